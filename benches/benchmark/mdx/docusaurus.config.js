@@ -0,0 +1,5 @@
+module.exports = {
+  title: 'Benchmark',
+  url: 'https://example.net',
+  baseUrl: '/',
+};
@@ -0,0 +1,3 @@
+export default function Hello({ children }) {
+  return <span>{children}</span>;
+}
@@ -0,0 +1,7 @@
+# Build script, see [the guide](../docs/guide.md#usage)
+
+URL = "# [not checked](missing.md)"
+
+
+def main():
+    print(URL)  # Settings are explained in [the guide](../docs/guide.md#settings)
@@ -0,0 +1,19 @@
+//! Example crate documented with [the guide](../docs/guide.md).
+//!
+//! See also the [usage section](../docs/guide.md#usage).
+
+/// A [`Config`] holds the [settings](../docs/guide.md#settings).
+///
+/// Not checked: [`Vec::new()`] and [the struct](crate::Config).
+pub struct Config;
+
+#[doc = "The [entry point](../docs/guide.md#usage)."]
+pub fn run() {
+    // A plain comment is no documentation: [not checked](missing.md)
+    let _url = "/// [not checked](missing.md)";
+}
+
+/**
+ * Block documentation links to the [guide](../docs/guide.md) as well.
+ */
+pub fn block() {}
@@ -0,0 +1,41 @@
+use super::asciidoc_anchor_extractor::AsciiDocAnchorExtractor;
+use super::html_anchor_extractor::HtmlAnchorExtractor;
+use super::markdown_anchor_extractor::{MarkdownAnchorExtractor, MdxAnchorExtractor};
+use super::slug::SlugDialect;
+use crate::markup::MarkupType;
+use std::collections::HashSet;
+
+/// Finds all anchors a link fragment may point to in the given markup `text`.
+/// Anchors of headings are generated the way `slug_dialect` does it.
+///
+/// Returns `None`, if the anchors of documents of this markup type can not be determined.
+#[must_use]
+pub fn find_anchors(
+    text: &str,
+    markup_type: MarkupType,
+    slug_dialect: SlugDialect,
+) -> Option<HashSet<String>> {
+    anchor_extractor_factory(markup_type, slug_dialect).map(|e| e.find_anchors(text))
+}
+
+fn anchor_extractor_factory(
+    markup_type: MarkupType,
+    slug_dialect: SlugDialect,
+) -> Option<Box<dyn AnchorExtractor>> {
+    match markup_type {
+        MarkupType::Markdown => Some(Box::new(MarkdownAnchorExtractor(slug_dialect))),
+        MarkupType::Mdx => Some(Box::new(MdxAnchorExtractor(slug_dialect))),
+        MarkupType::Html | MarkupType::Svg => Some(Box::new(HtmlAnchorExtractor())),
+        MarkupType::AsciiDoc => Some(Box::new(AsciiDocAnchorExtractor())),
+        MarkupType::Rst
+        | MarkupType::Org
+        | MarkupType::Latex
+        | MarkupType::Notebook
+        | MarkupType::SourceCode
+        | MarkupType::Plain => None,
+    }
+}
+
+pub trait AnchorExtractor {
+    fn find_anchors(&self, text: &str) -> HashSet<String>;
+}
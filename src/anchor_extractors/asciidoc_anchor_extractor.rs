@@ -0,0 +1,138 @@
+use crate::anchor_extractors::anchor_extractor::AnchorExtractor;
+use regex::Regex;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+pub struct AsciiDocAnchorExtractor();
+
+impl AnchorExtractor for AsciiDocAnchorExtractor {
+    fn find_anchors(&self, text: &str) -> HashSet<String> {
+        lazy_static! {
+            // `[[id]]`, `[[id,reftext]]`, `anchor:id[]`, `[#id.role]` and `[id=id]`
+            static ref ID_REGEX: Regex = Regex::new(
+                r#"\[\[([A-Za-z_:][\w:.-]*)(?:,[^\]]*)?\]\]|\banchor:([A-Za-z_:][\w:.-]*)\[|\[#([A-Za-z_:][\w:-]*)|\[(?:[^\]\n]*,)?\s*id=["']?([A-Za-z_:][\w:.-]*)"#
+            )
+            .unwrap();
+            static ref ATTRIBUTE_REGEX: Regex = Regex::new(r"^:(idprefix|idseparator):[ \t]*(.*?)\s*$").unwrap();
+            static ref SECTION_REGEX: Regex = Regex::new(r"^(?:={2,6}|#{2,6})[ \t]+(.+?)\s*$").unwrap();
+        }
+
+        let mut anchors: HashSet<String> = ID_REGEX
+            .captures_iter(text)
+            .filter_map(|caps| caps.iter().skip(1).flatten().next())
+            .map(|id| id.as_str().to_string())
+            .collect();
+
+        let mut id_prefix = "_".to_string();
+        let mut id_separator = "_".to_string();
+        let mut occurrences: HashMap<String, usize> = HashMap::new();
+        for line in text.lines() {
+            if let Some(caps) = ATTRIBUTE_REGEX.captures(line) {
+                let value = caps[2].trim_matches(|c| c == '\'' || c == '"').to_string();
+                if &caps[1] == "idprefix" {
+                    id_prefix = value;
+                } else {
+                    id_separator = value;
+                }
+            } else if let Some(caps) = SECTION_REGEX.captures(line) {
+                let id = section_id(&caps[1], &id_prefix, &id_separator);
+                let count = occurrences.entry(id.clone()).or_insert(0);
+                *count += 1;
+                if *count == 1 {
+                    anchors.insert(id);
+                } else {
+                    anchors.insert(format!("{id}{id_separator}{count}"));
+                }
+            }
+        }
+        anchors
+    }
+}
+
+/// Generates the id of a section the way Asciidoctor does it.
+fn section_id(title: &str, id_prefix: &str, id_separator: &str) -> String {
+    lazy_static! {
+        static ref INVALID_CHARS_REGEX: Regex = Regex::new(
+            r"<[^>]+>|&(?:[a-z][a-z]+\d{0,2}|#\d\d\d{0,4}|#x[\da-f][\da-f][\da-f]{0,3});|[^ \w.-]+"
+        )
+        .unwrap();
+    }
+    let title = title.to_lowercase();
+    let title = INVALID_CHARS_REGEX.replace_all(&title, "");
+    let mut id = String::with_capacity(title.len());
+    let mut after_separator = false;
+    for c in title.chars() {
+        if matches!(c, ' ' | '.' | '-') {
+            if !after_separator {
+                id.push_str(id_separator);
+            }
+            after_separator = true;
+        } else {
+            id.push(c);
+            after_separator = false;
+        }
+    }
+    if !id_separator.is_empty() {
+        id = id.trim_end_matches(id_separator).to_string();
+        if id_prefix.is_empty() {
+            id = id.trim_start_matches(id_separator).to_string();
+        }
+    }
+    format!("{id_prefix}{id}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntest::test_case;
+
+    #[test_case("== Usage", "_usage")]
+    #[test_case("=== Getting Started", "_getting_started")]
+    #[test_case("== The `code` heading", "_the_code_heading")]
+    #[test_case("== Version 1.2 - Final!", "_version_1_2_final")]
+    #[test_case("== Über Größen", "_über_größen")]
+    #[test_case("## Markdown style", "_markdown_style")]
+    fn section(input: &str, anchor: &str) {
+        let le = AsciiDocAnchorExtractor();
+        let result = le.find_anchors(input);
+        assert_eq!(HashSet::from([anchor.to_string()]), result);
+    }
+
+    #[test_case("[[install]]", "install")]
+    #[test_case("[[install,Installation]]", "install")]
+    #[test_case("Some anchor:install[] text", "install")]
+    #[test_case("[#install.role]", "install")]
+    #[test_case("[quote,id=install]", "install")]
+    #[test_case("[[[bib-ref]]] Some book", "bib-ref")]
+    fn explicit_id(input: &str, anchor: &str) {
+        let le = AsciiDocAnchorExtractor();
+        let result = le.find_anchors(input);
+        assert_eq!(HashSet::from([anchor.to_string()]), result);
+    }
+
+    #[test]
+    fn custom_prefix_and_separator() {
+        let le = AsciiDocAnchorExtractor();
+        let result = le.find_anchors(":idprefix:\n:idseparator: -\n\n== Getting Started");
+        assert_eq!(HashSet::from(["getting-started".to_string()]), result);
+    }
+
+    #[test]
+    fn duplicate_sections() {
+        let le = AsciiDocAnchorExtractor();
+        let result = le.find_anchors("== Usage\n\n== Usage\n\n== Usage");
+        let expected = HashSet::from([
+            "_usage".to_string(),
+            "_usage_2".to_string(),
+            "_usage_3".to_string(),
+        ]);
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn document_title_has_no_id() {
+        let le = AsciiDocAnchorExtractor();
+        let result = le.find_anchors("= Document Title\n\nText");
+        assert!(result.is_empty());
+    }
+}
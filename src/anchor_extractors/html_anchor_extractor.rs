@@ -0,0 +1,79 @@
+use crate::anchor_extractors::anchor_extractor::AnchorExtractor;
+use crate::link_extractors::html_tokenizer::start_tags;
+use std::collections::HashSet;
+
+pub struct HtmlAnchorExtractor();
+
+impl AnchorExtractor for HtmlAnchorExtractor {
+    fn find_anchors(&self, text: &str) -> HashSet<String> {
+        let mut anchors = HashSet::new();
+        for tag in start_tags(text) {
+            let is_anchor_tag = tag.name == "a";
+            for attribute in tag.attributes {
+                let is_anchor =
+                    attribute.name == "id" || (is_anchor_tag && attribute.name == "name");
+                if is_anchor && !attribute.value.is_empty() {
+                    anchors.insert(attribute.value);
+                }
+            }
+        }
+        anchors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntest::test_case;
+
+    #[test_case("<a id=\"foo\"></a>", test_name = "id_double_quoted")]
+    #[test_case("<a id='foo'></a>", test_name = "id_single_quoted")]
+    #[test_case("<a id=foo></a>", test_name = "id_unquoted")]
+    #[test_case("<a name=\"foo\"></a>", test_name = "name")]
+    #[test_case("<A NAME = \"foo\">Foo</A>", test_name = "uppercase")]
+    #[test_case("<a\nhref=\"#bar\"\nid=\"foo\">Foo</a>", test_name = "multi_line")]
+    #[test_case("<h2 id=\"foo\">Foo</h2>", test_name = "heading_id")]
+    #[test_case("<section class=\"x\" ID=\"foo\">", test_name = "section_id")]
+    #[test_case("<input name=\"bar\" id=\"foo\">", test_name = "input_name")]
+    #[test_case("<p title=\"a>b\" id=\"foo\">", test_name = "quoted_greater_than")]
+    #[test_case("<p id=\"f&#111;o\">", test_name = "character_reference")]
+    fn anchor(input: &str) {
+        let le = HtmlAnchorExtractor();
+        let result = le.find_anchors(input);
+        assert_eq!(HashSet::from(["foo".to_string()]), result);
+    }
+
+    #[test]
+    fn generated_docs() {
+        let le = HtmlAnchorExtractor();
+        let input = "<section id=\"method.parse\" class=\"method\">\n<h4 class=\"code-header\">\n<a href=\"#method.parse\" class=\"anchor\">§</a>\n</h4></section>\n<span id=\"module-foo\"></span><a name=\"legacy\"></a>";
+        let result = le.find_anchors(input);
+        let expected = HashSet::from([
+            "method.parse".to_string(),
+            "module-foo".to_string(),
+            "legacy".to_string(),
+        ]);
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn commented() {
+        let le = HtmlAnchorExtractor();
+        let result = le.find_anchors("<!-- <a id=\"foo\"></a> -->");
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn raw_text() {
+        let le = HtmlAnchorExtractor();
+        let result = le.find_anchors("<script>let s = '<a id=\"foo\">';</script>");
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn no_anchor() {
+        let le = HtmlAnchorExtractor();
+        let result = le.find_anchors("<a href=\"#foo\">Foo</a> <p data-id=\"bar\">B</p>");
+        assert!(result.is_empty());
+    }
+}
@@ -0,0 +1,170 @@
+use super::html_anchor_extractor::HtmlAnchorExtractor;
+use super::slug::{SlugDialect, Slugger};
+use crate::anchor_extractors::anchor_extractor::AnchorExtractor;
+use crate::link_extractors::front_matter::FrontMatter;
+use crate::link_extractors::mdx_link_extractor::MdxDocument;
+use pulldown_cmark::{Event, Options, Parser, Tag};
+use regex::Regex;
+use std::collections::HashSet;
+
+pub struct MarkdownAnchorExtractor(pub SlugDialect);
+
+impl AnchorExtractor for MarkdownAnchorExtractor {
+    fn find_anchors(&self, text: &str) -> HashSet<String> {
+        let html_extractor = HtmlAnchorExtractor();
+        let options = if self.0.supports_heading_ids() {
+            Options::ENABLE_HEADING_ATTRIBUTES
+        } else {
+            Options::empty()
+        };
+        let masked_text = FrontMatter::find(text).map(|f| f.mask(text));
+        let parser = Parser::new_ext(masked_text.as_deref().unwrap_or(text), options);
+
+        let mut anchors = HashSet::new();
+        let mut slugger = Slugger::new(self.0);
+        let mut heading_text: Option<String> = None;
+        let mut in_code_block = false;
+        for evt in parser {
+            match evt {
+                Event::Start(Tag::Heading(..)) => heading_text = Some(String::new()),
+                Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+                Event::End(Tag::CodeBlock(_)) => in_code_block = false,
+                Event::End(Tag::Heading(_level, id, _classes)) => {
+                    let text = heading_text.take().unwrap_or_default();
+                    match id {
+                        Some(id) => {
+                            anchors.insert(id.to_string());
+                        }
+                        None => {
+                            anchors.insert(slugger.slug(&text));
+                        }
+                    }
+                }
+                Event::Text(text) | Event::Code(text) => {
+                    if let Some(heading_text) = heading_text.as_mut() {
+                        heading_text.push_str(&text);
+                    } else if !in_code_block {
+                        anchors.extend(block_id(&text));
+                    }
+                }
+                Event::Html(html) => anchors.extend(html_extractor.find_anchors(&html)),
+                Event::Start(_)
+                | Event::End(_)
+                | Event::FootnoteReference(_)
+                | Event::SoftBreak
+                | Event::HardBreak
+                | Event::Rule
+                | Event::TaskListMarker(_) => (),
+            }
+        }
+        anchors
+    }
+}
+
+/// Finds the anchors of the markdown of MDX files, ignoring their JSX
+pub struct MdxAnchorExtractor(pub SlugDialect);
+
+impl AnchorExtractor for MdxAnchorExtractor {
+    fn find_anchors(&self, text: &str) -> HashSet<String> {
+        MarkdownAnchorExtractor(self.0).find_anchors(&MdxDocument::parse(text).markdown)
+    }
+}
+
+/// Block ids like `^summary` at the end of a paragraph or list item,
+/// which wiki links like `[[Page#^summary]]` refer to
+fn block_id(text: &str) -> Option<String> {
+    lazy_static! {
+        static ref BLOCK_ID_REGEX: Regex = Regex::new(r"(?:^|\s)(\^[[:alnum:]-]+)\s*$").unwrap();
+    }
+    BLOCK_ID_REGEX
+        .captures(text)
+        .map(|captures| captures[1].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntest::test_case;
+
+    #[test_case("# Chapter 1", "chapter-1")]
+    #[test_case("## Auto anchor 2", "auto-anchor-2")]
+    #[test_case("Setext Heading\n===", "setext-heading")]
+    #[test_case("## auto-anchor !@#$%^&*()_+`=3", "auto-anchor-_3")]
+    #[test_case("# The `code` heading", "the-code-heading")]
+    #[test_case("# Über Größen", "über-größen")]
+    #[test_case("### Manual Anchor {#man-anch-1}", "manual-anchor-man-anch-1")]
+    fn heading(input: &str, anchor: &str) {
+        let le = MarkdownAnchorExtractor(SlugDialect::GitHub);
+        let result = le.find_anchors(input);
+        assert_eq!(HashSet::from([anchor.to_string()]), result);
+    }
+
+    #[test_case("### Manual Anchor {#man-anch-1}", "man-anch-1")]
+    #[test_case("# Hello World", "hello-world")]
+    fn heading_with_ids(input: &str, anchor: &str) {
+        let le = MarkdownAnchorExtractor(SlugDialect::Docusaurus);
+        let result = le.find_anchors(input);
+        assert_eq!(HashSet::from([anchor.to_string()]), result);
+    }
+
+    #[test]
+    fn duplicate_headings() {
+        let le = MarkdownAnchorExtractor(SlugDialect::GitHub);
+        let result = le.find_anchors("# Foo\n\n# Foo\n\n# Foo 1\n\n## Foo");
+        let expected = HashSet::from([
+            "foo".to_string(),
+            "foo-1".to_string(),
+            "foo-1-1".to_string(),
+            "foo-2".to_string(),
+        ]);
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn html_anchor() {
+        let le = MarkdownAnchorExtractor(SlugDialect::GitHub);
+        let result = le.find_anchors("Some text\n\n<a name=\"foo\"></a>\n\n<a id=\"bar\"></a>");
+        let expected = HashSet::from(["foo".to_string(), "bar".to_string()]);
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn no_heading_in_code_block() {
+        let le = MarkdownAnchorExtractor(SlugDialect::GitHub);
+        let result = le.find_anchors("```\n# Foo\n```");
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn no_heading_in_front_matter() {
+        let le = MarkdownAnchorExtractor(SlugDialect::GitHub);
+        let result = le.find_anchors("---\ntitle: Foo\n---\n# Bar");
+        assert_eq!(HashSet::from(["bar".to_string()]), result);
+    }
+
+    #[test_case("Install the tools. ^summary", "^summary")]
+    #[test_case("* Item ^item-1\n* Other", "^item-1")]
+    #[test_case("> Quote\n\n^quote", "^quote")]
+    fn block_ids(input: &str, anchor: &str) {
+        let le = MarkdownAnchorExtractor(SlugDialect::GitHub);
+        let result = le.find_anchors(input);
+        assert_eq!(HashSet::from([anchor.to_string()]), result);
+    }
+
+    #[test_case("2^10")]
+    #[test_case("A ^summary here")]
+    #[test_case("```\ncode ^block\n```")]
+    fn no_block_ids(input: &str) {
+        let le = MarkdownAnchorExtractor(SlugDialect::GitHub);
+        let result = le.find_anchors(input);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn mdx_headings() {
+        let le = MdxAnchorExtractor(SlugDialect::Docusaurus);
+        let input = "import Tabs from '@theme/Tabs';\n\n<Tabs>\n    ## In Tab\n</Tabs>\n\n## Title {#custom}\n\n```\n# Code\n```";
+        let expected = HashSet::from(["in-tab".to_string(), "custom".to_string()]);
+        assert_eq!(expected, le.find_anchors(input));
+    }
+}
@@ -0,0 +1,5 @@
+pub mod anchor_extractor;
+mod asciidoc_anchor_extractor;
+mod html_anchor_extractor;
+mod markdown_anchor_extractor;
+pub mod slug;
@@ -0,0 +1,289 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// The way a markup renderer generates the anchors of headings
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum SlugDialect {
+    #[default]
+    GitHub,
+    GitLab,
+    MdBook,
+    Pandoc,
+    /// Also used by Jekyll
+    Kramdown,
+    Docusaurus,
+}
+
+impl FromStr for SlugDialect {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "github" => Ok(Self::GitHub),
+            "gitlab" => Ok(Self::GitLab),
+            "mdbook" => Ok(Self::MdBook),
+            "pandoc" => Ok(Self::Pandoc),
+            "kramdown" => Ok(Self::Kramdown),
+            "docusaurus" => Ok(Self::Docusaurus),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for SlugDialect {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::GitHub => "github",
+            Self::GitLab => "gitlab",
+            Self::MdBook => "mdbook",
+            Self::Pandoc => "pandoc",
+            Self::Kramdown => "kramdown",
+            Self::Docusaurus => "docusaurus",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl SlugDialect {
+    /// Whether explicit heading ids like `# Heading {#custom-id}` are supported.
+    #[must_use]
+    pub const fn supports_heading_ids(self) -> bool {
+        match self {
+            Self::GitHub | Self::GitLab => false,
+            Self::MdBook | Self::Pandoc | Self::Kramdown | Self::Docusaurus => true,
+        }
+    }
+
+    /// Converts the text of a heading into an anchor,
+    /// not taking care of duplicates.
+    #[must_use]
+    pub fn slug(self, text: &str) -> String {
+        match self {
+            Self::GitHub | Self::Docusaurus => text
+                .to_lowercase()
+                .chars()
+                .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_' || *c == ' ')
+                .map(|c| if c == ' ' { '-' } else { c })
+                .collect(),
+            Self::GitLab => {
+                let slug: String = text
+                    .trim()
+                    .to_lowercase()
+                    .chars()
+                    .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_' || *c == ' ')
+                    .map(|c| if c == ' ' { '-' } else { c })
+                    .collect();
+                let mut squeezed = String::with_capacity(slug.len());
+                for c in slug.chars() {
+                    if !(c == '-' && squeezed.ends_with('-')) {
+                        squeezed.push(c);
+                    }
+                }
+                squeezed
+            }
+            Self::MdBook => text
+                .trim()
+                .chars()
+                .filter_map(|c| {
+                    if c.is_alphanumeric() || c == '_' || c == '-' {
+                        Some(c.to_ascii_lowercase())
+                    } else if c.is_whitespace() {
+                        Some('-')
+                    } else {
+                        None
+                    }
+                })
+                .collect(),
+            Self::Pandoc => {
+                let filtered: String = text
+                    .to_lowercase()
+                    .chars()
+                    .filter(|c| {
+                        c.is_alphanumeric() || c.is_whitespace() || ['_', '-', '.'].contains(c)
+                    })
+                    .collect();
+                let slug = filtered.split_whitespace().collect::<Vec<_>>().join("-");
+                let slug = slug.trim_start_matches(|c: char| !c.is_alphabetic());
+                if slug.is_empty() {
+                    "section".to_string()
+                } else {
+                    slug.to_string()
+                }
+            }
+            Self::Kramdown => {
+                let slug: String = text
+                    .trim_start_matches(|c: char| !c.is_ascii_alphabetic())
+                    .chars()
+                    .filter(|c| c.is_ascii_alphanumeric() || *c == ' ' || *c == '-')
+                    .map(|c| {
+                        if c == ' ' {
+                            '-'
+                        } else {
+                            c.to_ascii_lowercase()
+                        }
+                    })
+                    .collect();
+                if slug.is_empty() {
+                    "section".to_string()
+                } else {
+                    slug
+                }
+            }
+        }
+    }
+}
+
+/// Generates the anchors of all the headings of a single document,
+/// taking care of duplicates the way the dialect does.
+pub struct Slugger {
+    dialect: SlugDialect,
+    occurrences: HashMap<String, usize>,
+}
+
+impl Slugger {
+    #[must_use]
+    pub fn new(dialect: SlugDialect) -> Self {
+        Self {
+            dialect,
+            occurrences: HashMap::new(),
+        }
+    }
+
+    pub fn slug(&mut self, text: &str) -> String {
+        let slug = self.dialect.slug(text);
+        match self.dialect {
+            SlugDialect::GitHub | SlugDialect::Docusaurus | SlugDialect::Pandoc => {
+                // Append a numeric suffix, until the anchor was not used before
+                let mut unique = slug.clone();
+                while self.occurrences.contains_key(&unique) {
+                    let count = self.occurrences.entry(slug.clone()).or_default();
+                    *count += 1;
+                    unique = format!("{slug}-{count}");
+                }
+                self.occurrences.insert(unique.clone(), 0);
+                unique
+            }
+            SlugDialect::GitLab | SlugDialect::MdBook | SlugDialect::Kramdown => {
+                // Append the number of previous headings with the same anchor
+                let count = self.occurrences.entry(slug.clone()).or_default();
+                let unique = if *count == 0 {
+                    slug
+                } else {
+                    format!("{slug}-{count}")
+                };
+                *count += 1;
+                unique
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntest::test_case;
+
+    fn slugs(dialect: SlugDialect, headings: &[&str]) -> Vec<String> {
+        let mut slugger = Slugger::new(dialect);
+        headings.iter().map(|h| slugger.slug(h)).collect()
+    }
+
+    #[test_case("Hello World", "hello-world")]
+    #[test_case("auto-anchor !@#$%^&*()_+`=3", "auto-anchor-_3")]
+    #[test_case("Über Größen", "über-größen")]
+    #[test_case("1. Introduction", "1-introduction")]
+    #[test_case("Version 2.0", "version-20")]
+    #[test_case("C++ & Rust", "c--rust")]
+    #[test_case("Foo -- Bar", "foo----bar")]
+    #[test_case("Manual Anchor {#man-anch-1}", "manual-anchor-man-anch-1")]
+    fn github(heading: &str, anchor: &str) {
+        assert_eq!(SlugDialect::GitHub.slug(heading), anchor);
+    }
+
+    #[test_case("Hello World", "hello-world")]
+    #[test_case("auto-anchor !@#$%^&*()_+`=3", "auto-anchor-_3")]
+    #[test_case("Über Größen", "über-größen")]
+    #[test_case("1. Introduction", "1-introduction")]
+    #[test_case("Version 2.0", "version-20")]
+    #[test_case("C++ & Rust", "c-rust")]
+    #[test_case("Foo -- Bar", "foo-bar")]
+    fn gitlab(heading: &str, anchor: &str) {
+        assert_eq!(SlugDialect::GitLab.slug(heading), anchor);
+    }
+
+    #[test_case("Hello World", "hello-world")]
+    #[test_case("auto-anchor !@#$%^&*()_+`=3", "auto-anchor-_3")]
+    #[test_case("Über Größen", "Über-größen")]
+    #[test_case("1. Introduction", "1-introduction")]
+    #[test_case("Version 2.0", "version-20")]
+    #[test_case("C++ & Rust", "c--rust")]
+    #[test_case("Foo -- Bar", "foo----bar")]
+    fn mdbook(heading: &str, anchor: &str) {
+        assert_eq!(SlugDialect::MdBook.slug(heading), anchor);
+    }
+
+    #[test_case("Hello World", "hello-world")]
+    #[test_case("auto-anchor !@#$%^&*()_+`=3", "auto-anchor-_3")]
+    #[test_case("2nd auto-anchor !@#$%^&*()_+`=3", "nd-auto-anchor-_3")]
+    #[test_case("Über Größen", "über-größen")]
+    #[test_case("1. Introduction", "introduction")]
+    #[test_case("Version 2.0", "version-2.0")]
+    #[test_case("C++ & Rust", "c-rust")]
+    #[test_case("123", "section")]
+    fn pandoc(heading: &str, anchor: &str) {
+        assert_eq!(SlugDialect::Pandoc.slug(heading), anchor);
+    }
+
+    #[test_case("Hello World", "hello-world")]
+    #[test_case("auto-anchor !@#$%^&*()_+`=3", "auto-anchor-3")]
+    #[test_case("Über Größen", "ber-gren")]
+    #[test_case("1. Introduction", "introduction")]
+    #[test_case("Version 2.0", "version-20")]
+    #[test_case("C++ & Rust", "c--rust")]
+    #[test_case("snake_case", "snakecase")]
+    #[test_case("123", "section")]
+    fn kramdown(heading: &str, anchor: &str) {
+        assert_eq!(SlugDialect::Kramdown.slug(heading), anchor);
+    }
+
+    #[test_case("Hello World", "hello-world")]
+    #[test_case("auto-anchor !@#$%^&*()_+`=3", "auto-anchor-_3")]
+    #[test_case("Über Größen", "über-größen")]
+    #[test_case("1. Introduction", "1-introduction")]
+    #[test_case("C++ & Rust", "c--rust")]
+    fn docusaurus(heading: &str, anchor: &str) {
+        assert_eq!(SlugDialect::Docusaurus.slug(heading), anchor);
+    }
+
+    #[test]
+    fn duplicates_github() {
+        let result = slugs(SlugDialect::GitHub, &["Foo", "Foo", "Foo 1", "Foo"]);
+        assert_eq!(result, vec!["foo", "foo-1", "foo-1-1", "foo-2"]);
+    }
+
+    #[test]
+    fn duplicates_pandoc() {
+        let result = slugs(SlugDialect::Pandoc, &["Foo", "Foo", "Foo 1", "Foo"]);
+        assert_eq!(result, vec!["foo", "foo-1", "foo-1-1", "foo-2"]);
+    }
+
+    #[test]
+    fn duplicates_gitlab() {
+        let result = slugs(SlugDialect::GitLab, &["Foo", "Foo", "Foo 1", "Foo"]);
+        assert_eq!(result, vec!["foo", "foo-1", "foo-1", "foo-2"]);
+    }
+
+    #[test]
+    fn duplicates_mdbook() {
+        let result = slugs(SlugDialect::MdBook, &["Foo", "Foo", "Foo"]);
+        assert_eq!(result, vec!["foo", "foo-1", "foo-2"]);
+    }
+
+    #[test]
+    fn duplicates_kramdown() {
+        let result = slugs(SlugDialect::Kramdown, &["Foo", "Foo", "1 Foo"]);
+        assert_eq!(result, vec!["foo", "foo-1", "foo-2"]);
+    }
+}
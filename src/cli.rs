@@ -5,6 +5,7 @@ use crate::Config;
 use crate::OptionalConfig;
 use clap::Arg;
 use clap::ArgAction;
+use clap::ArgMatches;
 use clap::Command;
 use const_format::formatcp;
 use std::convert::TryFrom;
@@ -51,17 +52,93 @@ fn print_version_and_exit(quiet: bool) {
     std::process::exit(0);
 }
 
-#[must_use]
-pub fn parse_args() -> Config {
-    let mut opt: OptionalConfig = match fs::read_to_string(CONFIG_FILE_PATH) {
-        Ok(content) => match toml::from_str(&content) {
-            Ok(o) => o,
-            Err(err) => panic!("Invalid TOML file {:?}", err),
-        },
-        Err(_) => OptionalConfig::default(),
-    };
+fn arg_link_kinds() -> Arg {
+    Arg::new("link-kinds")
+        .long("link-kinds")
+        .value_name("KINDS")
+        .help("List of link kinds which shall be checked; comma separated")
+        .long_help("One or more kinds of links which shall be checked, separated by commas. Links of other kinds are skipped, like all but images with --link-kinds image.")
+        .action(ArgAction::Append)
+        .value_delimiter(',')
+        .value_parser(["inline", "reference", "autolink", "image", "html"])
+        .required(false)
+}
+
+fn arg_unused_references() -> Arg {
+    Arg::new("unused-references")
+        .long("unused-references")
+        .num_args(1)
+        .value_name("SEVERITY")
+        .help("Severity of markdown reference definitions which are never used; default: warn")
+        .value_parser(["warn", "fail"])
+        .required(false)
+}
+
+fn arg_http_fragments() -> Arg {
+    Arg::new("http-fragments")
+        .long("http-fragments")
+        .short('f')
+        .num_args(1)
+        .value_name("SEVERITY")
+        .help("Check the fragments of web links against the ids of the fetched document")
+        .long_help("Check the fragments (aka anchors) of web links against the ids of the fetched HTML or markdown document, and report missing ones with the given severity.")
+        .value_parser(["warn", "fail"])
+        .required(false)
+}
+
+fn arg_slug_dialect() -> Arg {
+    Arg::new("slug-dialect")
+        .long("slug-dialect")
+        .short('s')
+        .num_args(1)
+        .value_name("DIALECT")
+        .help("The way anchors of markdown headings are generated; default: github")
+        .long_help("The way the anchors of markdown headings are generated, which link fragments are checked against. Choose the renderer the documents are published with; default: github")
+        .value_parser(["github", "gitlab", "mdbook", "pandoc", "kramdown", "docusaurus"])
+        .required(false)
+}
 
-    let matches = Command::new(crate_name!())
+fn arg_markdown_dialect() -> Arg {
+    Arg::new("markdown-dialect")
+        .long("markdown-dialect")
+        .num_args(1)
+        .value_name("DIALECT")
+        .help("The markdown flavor, which decides the supported syntax extensions; default: github")
+        .long_help("The markdown flavor of the checked files. The github dialect supports tables, footnotes, task lists, strikethrough and bare URLs, commonmark none of them; default: github")
+        .value_parser(["github", "commonmark"])
+        .required(false)
+}
+
+fn arg_front_matter_keys() -> Arg {
+    Arg::new("front-matter-keys")
+        .long("front-matter-keys")
+        .value_name("KEYS")
+        .help("List of front matter keys of markdown files, whose values are checked as links; space separated")
+        .long_help("One or more top level keys of the YAML or TOML front matter of markdown files, like redirect_from or image, whose values are checked as links, separated by white-space.")
+        .value_delimiter(',')
+        .action(ArgAction::Append)
+        .required(false)
+}
+
+fn arg_wiki_links() -> Arg {
+    Arg::new("wiki-links")
+        .long("wiki-links")
+        .help("Also check wiki links like [[Page Name]] of markdown files")
+        .long_help("Also check wiki links like [[Page Name]], [[Page#Heading|alias]] or ![[image.png]] of markdown files, as used by GitHub wikis, Obsidian and Foam. Pages are matched case-insensitively by their file name anywhere below the root directory.")
+        .action(ArgAction::SetTrue)
+        .required(false)
+}
+
+fn arg_notebook_outputs() -> Arg {
+    Arg::new("notebook-outputs")
+        .long("notebook-outputs")
+        .help("Also check the links in the HTML outputs of Jupyter notebook cells")
+        .action(ArgAction::SetTrue)
+        .required(false)
+}
+
+fn command() -> Command {
+    Command::new(crate_name!())
         .arg(
             Arg::new("directory")
                 .help("Check all links in given directory and subdirectories")
@@ -124,9 +201,10 @@ pub fn parse_args() -> Config {
                 .long_help("One or more markup file types which shall be checked, separated by white-space.")
                 .action(ArgAction::Append)
                 .value_delimiter(',')
-                .value_parser(["md", "html"])
+                .value_parser(["md", "mdx", "html", "rst", "adoc", "org", "tex", "ipynb", "svg", "code", "plain"])
                 .required(false)
         )
+        .arg(arg_link_kinds())
         .arg(
             Arg::new("throttle")
                 .long("throttle")
@@ -136,6 +214,13 @@ pub fn parse_args() -> Config {
                 .help("Wait between http request to the same host for a defined number of milliseconds")
                 .required(false)
         )
+        .arg(arg_http_fragments())
+        .arg(arg_unused_references())
+        .arg(arg_slug_dialect())
+        .arg(arg_markdown_dialect())
+        .arg(arg_front_matter_keys())
+        .arg(arg_wiki_links())
+        .arg(arg_notebook_outputs())
         .arg(
             Arg::new("root-dir")
                 .long("root-dir")
@@ -151,7 +236,19 @@ pub fn parse_args() -> Config {
         .disable_version_flag(true)
         .author(crate_authors!())
         .about(crate_description!())
-        .get_matches();
+}
+
+#[must_use]
+pub fn parse_args() -> Config {
+    let mut opt: OptionalConfig = match fs::read_to_string(CONFIG_FILE_PATH) {
+        Ok(content) => match toml::from_str(&content) {
+            Ok(o) => o,
+            Err(err) => panic!("Invalid TOML file {:?}", err),
+        },
+        Err(_) => OptionalConfig::default(),
+    };
+
+    let matches = command().get_matches();
 
     let quiet = matches.get_flag(A_L_QUIET);
     let version = matches.get_flag(A_L_VERSION);
@@ -168,6 +265,15 @@ pub fn parse_args() -> Config {
         .parse()
         .expect("failed to parse path");
 
+    apply_matches(&mut opt, &matches);
+
+    Config {
+        directory,
+        optional: opt,
+    }
+}
+
+fn apply_matches(opt: &mut OptionalConfig, matches: &ArgMatches) {
     if matches.get_flag("debug") {
         opt.debug = Some(true);
     }
@@ -218,8 +324,43 @@ pub fn parse_args() -> Config {
         opt.root_dir = Some(root_path);
     }
 
-    Config {
-        directory,
-        optional: opt,
+    apply_markup_matches(opt, matches);
+}
+
+fn apply_markup_matches(opt: &mut OptionalConfig, matches: &ArgMatches) {
+    if let Some(http_fragments) = matches.get_one::<String>("http-fragments") {
+        opt.http_fragments = Some(http_fragments.parse().expect("invalid severity"));
+    }
+
+    if let Some(unused_references) = matches.get_one::<String>("unused-references") {
+        opt.unused_references = Some(unused_references.parse().expect("invalid severity"));
+    }
+
+    if let Some(slug_dialect) = matches.get_one::<String>("slug-dialect") {
+        opt.slug_dialect = Some(slug_dialect.parse().expect("invalid slug dialect"));
+    }
+
+    if let Some(markdown_dialect) = matches.get_one::<String>("markdown-dialect") {
+        opt.markdown_dialect = Some(markdown_dialect.parse().expect("invalid markdown dialect"));
+    }
+
+    if let Some(front_matter_keys) = matches.get_many::<String>("front-matter-keys") {
+        opt.front_matter_keys = Some(front_matter_keys.map(ToString::to_string).collect());
+    }
+
+    if matches.get_flag("wiki-links") {
+        opt.wiki_links = Some(true);
+    }
+
+    if matches.get_flag("notebook-outputs") {
+        opt.notebook_outputs = Some(true);
+    }
+
+    if let Some(link_kinds) = matches.get_many::<String>("link-kinds") {
+        opt.link_kinds = Some(
+            link_kinds
+                .map(|v| v.as_str().parse().expect("invalid link kind"))
+                .collect(),
+        );
     }
 }
@@ -1,8 +1,10 @@
 extern crate walkdir;
 
+use crate::link_extractors::plain_link_extractor::plain_file_patterns;
 use crate::markup::{MarkupFile, MarkupType};
 use crate::Config;
 use std::fs;
+use std::path::Path;
 use walkdir::WalkDir;
 
 /// Searches for markup source files acording to the configuration,
@@ -28,7 +30,10 @@ pub fn find(config: &Config, result: &mut Vec<MarkupFile>) {
     {
         let f_name = entry.file_name().to_string_lossy();
 
-        if let Some(markup_type) = markup_type(&f_name, markup_types) {
+        let markup_type = markup_type(&f_name, markup_types)
+            .or_else(|| configured_source_code(&f_name, config, markup_types))
+            .or_else(|| configured_plain(entry.path(), config, markup_types));
+        if let Some(markup_type) = markup_type {
             let path = entry.path();
             let abs_path = fs::canonicalize(path).expect("Expected path to exist.");
             let ignore = if let Some(ignore_paths) = &config.optional.ignore_paths {
@@ -57,7 +62,7 @@ pub fn find(config: &Config, result: &mut Vec<MarkupFile>) {
 
 /// Identifies the markup type a file path belongs to,
 /// if any, out of a given set of markup types.
-fn markup_type(file: &str, markup_types: &[MarkupType]) -> Option<MarkupType> {
+pub(crate) fn markup_type(file: &str, markup_types: &[MarkupType]) -> Option<MarkupType> {
     let file_low = file.to_lowercase();
     for markup_type in markup_types {
         let extensions = markup_type.file_extensions();
@@ -72,3 +77,34 @@ fn markup_type(file: &str, markup_types: &[MarkupType]) -> Option<MarkupType> {
 
     None
 }
+
+/// Files with a configured `comment-syntax` are source code files.
+fn configured_source_code(
+    file: &str,
+    config: &Config,
+    markup_types: &[MarkupType],
+) -> Option<MarkupType> {
+    if !markup_types.contains(&MarkupType::SourceCode) {
+        return None;
+    }
+    let file_low = file.to_lowercase();
+    config
+        .optional
+        .comment_syntax
+        .as_ref()?
+        .keys()
+        .any(|ext| file_low.ends_with(&format!(".{}", ext.to_lowercase())))
+        .then_some(MarkupType::SourceCode)
+}
+
+/// Files matching a glob of the `plain-files` option are plain files.
+fn configured_plain(
+    path: &Path,
+    config: &Config,
+    markup_types: &[MarkupType],
+) -> Option<MarkupType> {
+    if !markup_types.contains(&MarkupType::Plain) {
+        return None;
+    }
+    plain_file_patterns(&path.to_string_lossy(), config).map(|_| MarkupType::Plain)
+}
@@ -34,10 +34,18 @@ extern crate clap;
 #[macro_use]
 extern crate lazy_static;
 
+use crate::anchor_extractors::slug::SlugDialect;
+use crate::link_extractors::link_extractor::LinkIssue;
+use crate::link_extractors::link_extractor::LinkKind;
 use crate::link_extractors::link_extractor::MarkupLink;
-use crate::link_validator::link_type::get_link_type;
+use crate::link_validator::get_fragment;
+use crate::link_validator::get_link_type_with_base;
 use crate::link_validator::link_type::LinkType;
 use crate::link_validator::resolve_target_link;
+use crate::link_validator::Docusaurus;
+use crate::link_validator::Latex;
+use crate::link_validator::Vault;
+use crate::markup::MarkdownDialect;
 use crate::markup::MarkupFile;
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -47,6 +55,7 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use tokio::time::{sleep_until, Duration, Instant};
+pub mod anchor_extractors;
 pub mod cli;
 pub mod file_traversal;
 pub mod ignore_path;
@@ -61,6 +70,8 @@ use futures::{stream, StreamExt};
 use git_version::git_version;
 use ignore_path::IgnorePath;
 use link_validator::LinkCheckResult;
+use link_validator::Severity;
+use regex::Regex;
 use url::Url;
 
 pub const VERSION: &str = git_version!();
@@ -83,6 +94,30 @@ pub struct OptionalConfig {
     #[serde(rename(deserialize = "root-dir"))]
     pub root_dir: Option<PathBuf>,
     pub throttle: Option<u32>,
+    #[serde(rename(deserialize = "http-fragments"))]
+    pub http_fragments: Option<Severity>,
+    #[serde(rename(deserialize = "slug-dialect"))]
+    pub slug_dialect: Option<SlugDialect>,
+    #[serde(rename(deserialize = "markdown-dialect"))]
+    pub markdown_dialect: Option<MarkdownDialect>,
+    #[serde(rename(deserialize = "front-matter-keys"))]
+    pub front_matter_keys: Option<Vec<String>>,
+    #[serde(rename(deserialize = "wiki-links"))]
+    pub wiki_links: Option<bool>,
+    #[serde(rename(deserialize = "notebook-outputs"))]
+    pub notebook_outputs: Option<bool>,
+    #[serde(rename(deserialize = "unused-references"))]
+    pub unused_references: Option<Severity>,
+    #[serde(rename(deserialize = "comment-syntax"))]
+    pub comment_syntax: Option<HashMap<String, Vec<String>>>,
+    #[serde(
+        rename(deserialize = "plain-files"),
+        default,
+        deserialize_with = "link_extractors::plain_link_extractor::deserialize_plain_files"
+    )]
+    pub plain_files: Option<HashMap<String, Vec<Regex>>>,
+    #[serde(rename(deserialize = "link-kinds"))]
+    pub link_kinds: Option<Vec<LinkKind>>,
 }
 
 #[derive(Default, Debug, Deserialize)]
@@ -109,6 +144,27 @@ impl fmt::Display for Config {
             Some(p) => p.iter().map(|m| format!("{m:?}")).collect(),
             None => vec![],
         };
+        let comment_syntax_str: Vec<String> = match &self.optional.comment_syntax {
+            Some(s) => s
+                .iter()
+                .map(|(ext, markers)| format!("{ext}={}", markers.join(",")))
+                .collect(),
+            None => vec![],
+        };
+        let plain_files_str: Vec<String> = match &self.optional.plain_files {
+            Some(p) => p
+                .iter()
+                .map(|(glob, patterns)| {
+                    let patterns: Vec<&str> = patterns.iter().map(Regex::as_str).collect();
+                    format!("{glob}={}", patterns.join(","))
+                })
+                .collect(),
+            None => vec![],
+        };
+        let link_kinds_str: Vec<String> = match &self.optional.link_kinds {
+            Some(k) => k.iter().map(ToString::to_string).collect(),
+            None => vec![],
+        };
         write!(
             f,
             "
@@ -120,7 +176,17 @@ MatchExt: {}
 RootDir: {}
 IgnoreLinks: {} 
 IgnorePaths: {:?}
-Throttle: {} ms",
+Throttle: {} ms
+HttpFragments: {}
+SlugDialect: {}
+MarkdownDialect: {}
+FrontMatterKeys: {}
+WikiLinks: {}
+NotebookOutputs: {}
+UnusedReferences: {}
+CommentSyntax: {}
+PlainFiles: {}
+LinkKinds: {}",
             self.optional.debug.unwrap_or(false),
             self.directory.to_str().unwrap_or_default(),
             markup_types_str,
@@ -129,7 +195,23 @@ Throttle: {} ms",
             root_dir_str,
             ignore_str.join(","),
             ignore_path_str,
-            self.optional.throttle.unwrap_or(0)
+            self.optional.throttle.unwrap_or(0),
+            self.optional
+                .http_fragments
+                .map_or_else(String::new, |s| s.to_string()),
+            self.optional.slug_dialect.unwrap_or_default(),
+            self.optional.markdown_dialect.unwrap_or_default(),
+            self.optional
+                .front_matter_keys
+                .as_deref()
+                .unwrap_or_default()
+                .join(", "),
+            self.optional.wiki_links.unwrap_or_default(),
+            self.optional.notebook_outputs.unwrap_or_default(),
+            self.optional.unused_references.unwrap_or(Severity::Warn),
+            comment_syntax_str.join(";"),
+            plain_files_str.join(";"),
+            link_kinds_str.join(",")
         )
     }
 }
@@ -142,8 +224,10 @@ struct FinalResult {
 
 #[derive(Hash, PartialEq, Eq, Clone, Debug)]
 struct Target {
-    target: String,
+    url: String,
     link_type: LinkType,
+    fragment: Option<String>,
+    issue: Option<LinkIssue>,
 }
 
 fn find_all_links(config: &Config) -> Vec<MarkupLink> {
@@ -151,7 +235,9 @@ fn find_all_links(config: &Config) -> Vec<MarkupLink> {
     file_traversal::find(config, &mut files);
     let mut links = vec![];
     for file in files {
-        links.append(&mut link_extractors::link_extractor::find_links(&file));
+        links.append(&mut link_extractors::link_extractor::find_links(
+            &file, config,
+        ));
     }
     links
 }
@@ -163,8 +249,12 @@ fn print_helper(
     error_channel: bool,
 ) {
     let link_str = format!(
-        "[{:^4}] {} ({}, {}) => {} - {}",
-        status_code, link.source, link.line, link.column, link.target, msg
+        "[{:^4}] {} ({}) => {} - {}",
+        status_code,
+        link.source,
+        link.position_str(),
+        link.target,
+        msg
     );
     if error_channel {
         eprintln!("{link_str}");
@@ -173,25 +263,103 @@ fn print_helper(
     }
 }
 
+/// The location of a link in GitHub workflow commands.
+/// Lines of notebook cells have no counterpart in the file, so only the file is given.
+fn github_location(link: &MarkupLink) -> String {
+    match link.cell {
+        Some(_) => format!("file={}", link.source),
+        None => format!(
+            "file={},line={},col={}",
+            link.source, link.line, link.column
+        ),
+    }
+}
+
 fn print_result(result: &FinalResult, map: &HashMap<Target, Vec<MarkupLink>>) {
     for link in &map[&result.target] {
         let code = &result.result_code;
-        print_helper(link, code.status_code(), code.msg(), code.has_issue());
+        // The links to a target may be images or not, so the message is built per link
+        let msg = if matches!(code, LinkCheckResult::Failed(_)) && link.kind == LinkKind::Image {
+            format!("Broken image. {}", code.msg())
+        } else {
+            code.msg().to_string()
+        };
+        print_helper(link, code.status_code(), &msg, code.has_issue());
     }
 }
 
-pub async fn run(config: &Config) -> Result<(), ()> {
-    let links = find_all_links(config);
+/// The target a link is checked against, which is shared by all links pointing to it
+async fn resolve_target(
+    link: &MarkupLink,
+    vault: Option<&Vault>,
+    docusaurus: &mut Docusaurus,
+    latex: &Latex,
+    config: &Config,
+) -> Target {
+    let link_type = get_link_type_with_base(link);
+    let slug_dialect = config.optional.slug_dialect.unwrap_or_default();
+    if link.issue.is_some() {
+        Target {
+            url: link.target.clone(),
+            link_type,
+            fragment: None,
+            issue: link.issue.clone(),
+        }
+    } else if let Some((url, fragment)) = vault.and_then(|vault| vault.resolve(link, slug_dialect))
+    {
+        Target {
+            url,
+            link_type: LinkType::FileSystem,
+            fragment,
+            issue: None,
+        }
+    } else if let Some(url) = docusaurus.resolve(link, config) {
+        Target {
+            url,
+            fragment: get_fragment(link, &LinkType::FileSystem, config),
+            link_type: LinkType::FileSystem,
+            issue: None,
+        }
+    } else if let Some(url) = latex.resolve(link) {
+        Target {
+            url,
+            fragment: get_fragment(link, &LinkType::FileSystem, config),
+            link_type: LinkType::FileSystem,
+            issue: None,
+        }
+    } else {
+        Target {
+            url: resolve_target_link(link, &link_type, config).await,
+            fragment: get_fragment(link, &link_type, config),
+            link_type,
+            issue: None,
+        }
+    }
+}
+
+/// Groups the links by the target they are checked against, and counts the skipped links
+async fn group_links(
+    links: &[MarkupLink],
+    config: &Config,
+) -> (HashMap<Target, Vec<MarkupLink>>, usize) {
     let mut link_target_groups: HashMap<Target, Vec<MarkupLink>> = HashMap::new();
 
     let mut skipped = 0;
 
+    let vault = config
+        .optional
+        .wiki_links
+        .unwrap_or_default()
+        .then(|| Vault::new(config));
+    let mut docusaurus = Docusaurus::default();
+    let latex = Latex::new(links);
+
     let ignore_links = config
         .optional
         .ignore_links
         .as_ref()
         .map_or_else(Vec::new, |s| s.iter().map(|m| WildMatch::new(m)).collect());
-    for link in &links {
+    for link in links {
         if ignore_links.iter().any(|m| m.matches(&link.target)) {
             print_helper(
                 link,
@@ -202,9 +370,19 @@ pub async fn run(config: &Config) -> Result<(), ()> {
             skipped += 1;
             continue;
         }
-        let link_type = get_link_type(&link.target);
-        let target = resolve_target_link(link, &link_type, config).await;
-        let t = Target { target, link_type };
+        if let Some(link_kinds) = &config.optional.link_kinds {
+            if !link_kinds.contains(&link.kind) {
+                print_helper(
+                    link,
+                    &"Skip".green(),
+                    "Ignore link because of link-kinds option.",
+                    false,
+                );
+                skipped += 1;
+                continue;
+            }
+        }
+        let t = resolve_target(link, vault.as_ref(), &mut docusaurus, &latex, config).await;
         match link_target_groups.get_mut(&t) {
             Some(v) => v.push(link.clone()),
             None => {
@@ -212,6 +390,81 @@ pub async fn run(config: &Config) -> Result<(), ()> {
             }
         }
     }
+    (link_target_groups, skipped)
+}
+
+/// Checks a target, after waiting for the throttle delay of its host
+async fn check_target(
+    target: &Target,
+    waits: Arc<Mutex<HashMap<String, Instant>>>,
+    throttle: bool,
+    config: &Config,
+) -> FinalResult {
+    if let Some(issue) = &target.issue {
+        return FinalResult {
+            target: target.clone(),
+            result_code: link_validator::issue_result(issue, config),
+        };
+    }
+    if throttle && target.link_type == LinkType::Http {
+        let parsed = match Url::parse(&target.url) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                return FinalResult {
+                    target: target.clone(),
+                    result_code: LinkCheckResult::Failed(format!(
+                        "Could not parse URL type. Err: {error:?}"
+                    )),
+                }
+            }
+        };
+        let host = match parsed.host_str() {
+            Some(host) => host.to_string(),
+            None => {
+                return FinalResult {
+                    target: target.clone(),
+                    result_code: LinkCheckResult::Failed("Failed to determine host".to_string()),
+                }
+            }
+        };
+        let mut waits = waits.lock().await;
+
+        let mut wait_until: Option<Instant> = None;
+        let next_wait = match waits.get(&host) {
+            Some(old) => {
+                wait_until = Some(*old);
+                *old + Duration::from_millis(config.optional.throttle.unwrap_or_default().into())
+            }
+            None => {
+                Instant::now()
+                    + Duration::from_millis(config.optional.throttle.unwrap_or_default().into())
+            }
+        };
+        waits.insert(host, next_wait);
+        drop(waits);
+
+        if let Some(deadline) = wait_until {
+            sleep_until(deadline).await;
+        }
+    }
+
+    let result_code = link_validator::check(
+        &target.url,
+        &target.link_type,
+        target.fragment.as_deref(),
+        config,
+    )
+    .await;
+
+    FinalResult {
+        target: target.clone(),
+        result_code,
+    }
+}
+
+pub async fn run(config: &Config) -> Result<(), ()> {
+    let links = find_all_links(config);
+    let (link_target_groups, mut skipped) = group_links(&links, config).await;
 
     let throttle = config.optional.throttle.unwrap_or_default() > 0;
     info!("Throttle HTTP requests to same host: {:?}", throttle);
@@ -220,63 +473,7 @@ pub async fn run(config: &Config) -> Result<(), ()> {
     let mut buffered_stream = stream::iter(link_target_groups.keys())
         .map(|target| {
             let waits = waits.clone();
-            async move {
-                if throttle && target.link_type == LinkType::Http {
-                    let parsed = match Url::parse(&target.target) {
-                        Ok(parsed) => parsed,
-                        Err(error) => {
-                            return FinalResult {
-                                target: target.clone(),
-                                result_code: LinkCheckResult::Failed(format!(
-                                    "Could not parse URL type. Err: {error:?}"
-                                )),
-                            }
-                        }
-                    };
-                    let host = match parsed.host_str() {
-                        Some(host) => host.to_string(),
-                        None => {
-                            return FinalResult {
-                                target: target.clone(),
-                                result_code: LinkCheckResult::Failed(
-                                    "Failed to determine host".to_string(),
-                                ),
-                            }
-                        }
-                    };
-                    let mut waits = waits.lock().await;
-
-                    let mut wait_until: Option<Instant> = None;
-                    let next_wait = match waits.get(&host) {
-                        Some(old) => {
-                            wait_until = Some(*old);
-                            *old + Duration::from_millis(
-                                config.optional.throttle.unwrap_or_default().into(),
-                            )
-                        }
-                        None => {
-                            Instant::now()
-                                + Duration::from_millis(
-                                    config.optional.throttle.unwrap_or_default().into(),
-                                )
-                        }
-                    };
-                    waits.insert(host, next_wait);
-                    drop(waits);
-
-                    if let Some(deadline) = wait_until {
-                        sleep_until(deadline).await;
-                    }
-                }
-
-                let result_code =
-                    link_validator::check(&target.target, &target.link_type, config).await;
-
-                FinalResult {
-                    target: target.clone(),
-                    result_code,
-                }
-            }
+            check_target(target, waits, throttle, config)
         })
         .buffer_unordered(PARALLEL_REQUESTS);
 
@@ -300,8 +497,10 @@ pub async fn run(config: &Config) -> Result<(), ()> {
                 if is_github_runner_env {
                     for link in &link_target_groups[&result.target] {
                         println!(
-                            "::warning file={},line={},col={},title=link checker warning::{}. {}",
-                            link.source, link.line, link.column, result.target.target, msg
+                            "::warning {},title=link checker warning::{}. {}",
+                            github_location(link),
+                            result.target.url,
+                            msg
                         );
                     }
                 }
@@ -314,8 +513,10 @@ pub async fn run(config: &Config) -> Result<(), ()> {
                 if is_github_runner_env {
                     for link in &link_target_groups[&result.target] {
                         println!(
-                            "::error file={},line={},col={},title=broken link::{}. {}",
-                            link.source, link.line, link.column, result.target.target, msg
+                            "::error {},title=broken link::{}. {}",
+                            github_location(link),
+                            result.target.url,
+                            msg
                         );
                     }
                 }
@@ -0,0 +1,328 @@
+use crate::link_extractors::link_extractor::line_column_from_idx;
+use crate::link_extractors::link_extractor::trim_url;
+use crate::link_extractors::link_extractor::LinkExtractor;
+use crate::link_extractors::link_extractor::LinkKind;
+use crate::link_extractors::link_extractor::MarkupLink;
+use crate::link_extractors::link_extractor::Span;
+use regex::Regex;
+use std::ops::Range;
+use std::path::Path;
+
+pub struct AsciiDocLinkExtractor();
+
+lazy_static! {
+    // Delimiters of listing, literal, comment and passthrough blocks
+    static ref VERBATIM_DELIMITER_REGEX: Regex = Regex::new(r"^(-{4,}|\.{4,}|/{4,}|\+{4,})\s*$").unwrap();
+    static ref IMAGESDIR_REGEX: Regex = Regex::new(r"(?m)^:imagesdir:[ \t]*(.*?)\s*$").unwrap();
+    static ref INCLUDE_REGEX: Regex = Regex::new(r"(?m)^include::([^\[\n]+)\[").unwrap();
+    static ref IMAGE_REGEX: Regex = Regex::new(r"\bimage::?([^\s\[]+)\[").unwrap();
+    static ref LINK_REGEX: Regex =
+        Regex::new(r"\b(link|mailto):(\+\+[^\n]+?\+\+|[^\s\[]+)\[").unwrap();
+    static ref XREF_REGEX: Regex = Regex::new(r"\bxref:([^\s\[]+)\[").unwrap();
+    static ref CROSS_REFERENCE_REGEX: Regex = Regex::new(r"<<([^>,\n]+?)\s*(?:,[^>]*)?>>").unwrap();
+    static ref URL_REGEX: Regex =
+        Regex::new(r#"(?m)(?:^|[^\w/:"'=\[+\\])((?:https?|ftp|irc)://[^\s\[\]<>"]+)"#).unwrap();
+}
+
+impl LinkExtractor for AsciiDocLinkExtractor {
+    fn find_links(&self, text: &str) -> Vec<MarkupLink> {
+        let text_without_verbatim = mask_verbatim(text);
+        let text = text_without_verbatim.as_str();
+        let imagesdirs: Vec<(usize, &str)> = IMAGESDIR_REGEX
+            .captures_iter(text)
+            .map(|caps| (caps.get(0).unwrap().start(), caps.get(1).unwrap().as_str()))
+            .collect();
+
+        // The byte index of the macro, the byte range of its target, the target and the kind
+        let mut result: Vec<(usize, Range<usize>, String, LinkKind)> = Vec::new();
+        for caps in INCLUDE_REGEX.captures_iter(text) {
+            let start = caps.get(0).unwrap().start();
+            let range = caps.get(1).unwrap().range();
+            result.push((start, range, caps[1].to_string(), LinkKind::Inline));
+        }
+        for caps in IMAGE_REGEX.captures_iter(text) {
+            let start = caps.get(0).unwrap().start();
+            let target = &caps[1];
+            let range = caps.get(1).unwrap().range();
+            let imagesdir = imagesdirs
+                .iter()
+                .rev()
+                .find(|(idx, _)| *idx < start)
+                .map_or("", |(_, dir)| *dir);
+            if imagesdir.is_empty() || target.starts_with('/') || target.contains("://") {
+                result.push((start, range, target.to_string(), LinkKind::Image));
+            } else {
+                result.push((
+                    start,
+                    range,
+                    format!("{}/{}", imagesdir.trim_end_matches('/'), target),
+                    LinkKind::Image,
+                ));
+            }
+        }
+        for caps in LINK_REGEX.captures_iter(text) {
+            let raw = caps.get(2).unwrap();
+            let target = raw.as_str().trim_matches('+');
+            let target_start =
+                raw.start() + raw.as_str().len() - raw.as_str().trim_start_matches('+').len();
+            let range = target_start..target_start + target.len();
+            let target = if &caps[1] == "mailto" {
+                format!("mailto:{target}")
+            } else {
+                target.to_string()
+            };
+            result.push((
+                caps.get(0).unwrap().start(),
+                range,
+                target,
+                LinkKind::Inline,
+            ));
+        }
+        for caps in XREF_REGEX
+            .captures_iter(text)
+            .chain(CROSS_REFERENCE_REGEX.captures_iter(text))
+        {
+            let start = caps.get(0).unwrap().start();
+            let range = caps.get(1).unwrap().range();
+            result.push((start, range, xref_target(&caps[1]), LinkKind::Inline));
+        }
+        for caps in URL_REGEX.captures_iter(text) {
+            let url = caps.get(1).unwrap();
+            let url_str = trim_url(url.as_str()).to_string();
+            let range = url.start()..url.start() + url_str.len();
+            result.push((url.start(), range, url_str, LinkKind::Autolink));
+        }
+
+        result.sort_by_key(|(idx, ..)| *idx);
+        result
+            .into_iter()
+            // Escaped macros and attribute references are no links
+            .filter(|(idx, _, target, _)| !text[..*idx].ends_with('\\') && !target.contains('{'))
+            .map(|(idx, range, target, kind)| {
+                let (line, column) = line_column_from_idx(text, idx);
+                MarkupLink {
+                    line,
+                    column,
+                    target,
+                    kind,
+                    span: Span::new(text, range),
+                    ..MarkupLink::default()
+                }
+            })
+            .collect()
+    }
+}
+
+/// Replaces the content of line comments and verbatim blocks with white-space,
+/// keeping the byte offsets of the remaining text intact.
+fn mask_verbatim(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    // The delimiter closing the current verbatim block
+    let mut open_delimiter: Option<&str> = None;
+    // Literal paragraphs start with an indented line and end at the next blank line
+    let mut in_literal_paragraph = false;
+    let mut after_blank_line = true;
+    for (line_idx, line) in text.split('\n').enumerate() {
+        if line_idx > 0 {
+            result.push('\n');
+        }
+        let trimmed = line.trim_end();
+        let blank = trimmed.is_empty();
+        let masked = if let Some(delimiter) = open_delimiter {
+            if trimmed == delimiter {
+                open_delimiter = None;
+            }
+            true
+        } else if VERBATIM_DELIMITER_REGEX.is_match(line) {
+            open_delimiter = Some(trimmed);
+            true
+        } else {
+            in_literal_paragraph = !blank
+                && (in_literal_paragraph
+                    || (after_blank_line && line.starts_with(char::is_whitespace)));
+            in_literal_paragraph || (line.starts_with("//") && !line.starts_with("///"))
+        };
+        after_blank_line = blank;
+
+        if masked {
+            result.push_str(&" ".repeat(line.len()));
+        } else {
+            result.push_str(line);
+        }
+    }
+    result
+}
+
+/// Cross references without a document refer to an id of the same document.
+fn xref_target(target: &str) -> String {
+    let target = target.trim_end_matches('#');
+    if target.contains('#')
+        || Path::new(target)
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("adoc"))
+    {
+        target.to_string()
+    } else {
+        format!("#{target}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::link_extractors::link_extractor::link;
+    use crate::link_extractors::link_extractor::span_texts;
+    use ntest::test_case;
+
+    fn kind_link(kind: LinkKind, target: &str, line: usize, column: usize) -> MarkupLink {
+        MarkupLink {
+            kind,
+            ..link(target, line, column)
+        }
+    }
+
+    #[test]
+    fn no_link() {
+        let le = AsciiDocLinkExtractor();
+        let input = "= Title\n\nThis is *no* link, just _text_ and a `link`.";
+        let result = le.find_links(input);
+        assert!(result.is_empty());
+    }
+
+    #[test_case("link:other/doc.html[Other]", "other/doc.html", 1, "other/doc.html")]
+    #[test_case(
+        "See link:https://example.net/[the page].",
+        "https://example.net/",
+        5,
+        "https://example.net/"
+    )]
+    #[test_case(
+        "link:++https://example.net/a b++[Spaces]",
+        "https://example.net/a b",
+        1,
+        "https://example.net/a b"
+    )]
+    #[test_case(
+        "Mail mailto:me@example.net[me]",
+        "mailto:me@example.net",
+        6,
+        "me@example.net"
+    )]
+    #[test_case("Umlaut ü link:doc.html[]", "doc.html", 10, "doc.html")]
+    fn link_macro(input: &str, target: &str, column: usize, raw: &str) {
+        let le = AsciiDocLinkExtractor();
+        let result = le.find_links(input);
+        assert_eq!(vec![link(target, 1, column).spanned_as(input, raw)], result);
+    }
+
+    #[test_case("xref:other.adoc#usage[Usage]", "other.adoc#usage", "other.adoc#usage")]
+    #[test_case("xref:other.adoc#[Other]", "other.adoc", "other.adoc#")]
+    #[test_case("xref:other#usage[]", "other#usage", "other#usage")]
+    #[test_case("xref:usage[Usage]", "#usage", "usage")]
+    #[test_case("<<usage>>", "#usage", "usage")]
+    #[test_case("<<usage,Usage>>", "#usage", "usage")]
+    #[test_case("<<other.adoc#usage,Usage>>", "other.adoc#usage", "other.adoc#usage")]
+    fn cross_reference(input: &str, target: &str, raw: &str) {
+        let le = AsciiDocLinkExtractor();
+        let result = le.find_links(input);
+        assert_eq!(vec![link(target, 1, 1).spanned_as(input, raw)], result);
+    }
+
+    #[test_case("image::images/logo.png[Logo]", "images/logo.png", true)]
+    #[test_case("image:logo.png[Logo,32]", "logo.png", true)]
+    #[test_case("include::chapters/intro.adoc[]", "chapters/intro.adoc", false)]
+    #[test_case(
+        "image::https://example.net/logo.png[]",
+        "https://example.net/logo.png",
+        true
+    )]
+    fn block_macro(input: &str, target: &str, image: bool) {
+        let le = AsciiDocLinkExtractor();
+        let result = le.find_links(input);
+        let kind = if image {
+            LinkKind::Image
+        } else {
+            LinkKind::Inline
+        };
+        assert_eq!(vec![kind_link(kind, target, 1, 1).spanned(input)], result);
+    }
+
+    #[test]
+    fn imagesdir() {
+        let le = AsciiDocLinkExtractor();
+        let input = "image::a.png[]\n:imagesdir: images\nimage::b.png[]\nimage::/abs/c.png[]";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![
+                kind_link(LinkKind::Image, "a.png", 1, 1).spanned(input),
+                kind_link(LinkKind::Image, "images/b.png", 3, 1).spanned_as(input, "b.png"),
+                kind_link(LinkKind::Image, "/abs/c.png", 4, 1).spanned(input)
+            ],
+            result
+        );
+    }
+
+    #[test_case("https://example.net/", "https://example.net/", 1)]
+    #[test_case("Visit https://example.net/.", "https://example.net/", 7)]
+    #[test_case("Visit https://example.net/[the page]", "https://example.net/", 7)]
+    #[test_case("(see https://example.net/)", "https://example.net/", 6)]
+    #[test_case("See <https://example.net/>", "https://example.net/", 6)]
+    #[test_case(
+        "Wiki https://en.wikipedia.org/wiki/Rust_(programming_language)",
+        "https://en.wikipedia.org/wiki/Rust_(programming_language)",
+        6
+    )]
+    fn bare_url(input: &str, target: &str, column: usize) {
+        let le = AsciiDocLinkExtractor();
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![kind_link(LinkKind::Autolink, target, 1, column).spanned(input)],
+            result
+        );
+    }
+
+    #[test_case(r"Escaped \https://example.net/")]
+    #[test_case(r"Escaped \link:doc.html[]")]
+    #[test_case("Attribute link:{docs-url}/doc.html[]")]
+    fn no_link_cases(input: &str) {
+        let le = AsciiDocLinkExtractor();
+        let result = le.find_links(input);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn verbatim_blocks() {
+        let le = AsciiDocLinkExtractor();
+        let input = "----\nlink:a.html[]\n----\n// link:b.html[]\n////\nlink:c.html[]\n////\n\n  link:d.html[]\n\nlink:e.html[]";
+        let result = le.find_links(input);
+        assert_eq!(vec![link("e.html", 11, 1).spanned(input)], result);
+    }
+
+    #[test]
+    fn multiple_links() {
+        let le = AsciiDocLinkExtractor();
+        let input = "See <<intro>> and\nxref:usage.adoc[] or https://example.net";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![
+                link("#intro", 1, 5).spanned_as(input, "intro"),
+                link("usage.adoc", 2, 1).spanned(input),
+                kind_link(LinkKind::Autolink, "https://example.net", 2, 22).spanned(input)
+            ],
+            result
+        );
+    }
+
+    #[test_case("include::chapters/intro.adoc[]", "chapters/intro.adoc")]
+    #[test_case(":imagesdir: images\n\nimage::logo.png[]", "logo.png")]
+    #[test_case("link:++https://example.net/a b++[Spaces]", "https://example.net/a b")]
+    #[test_case("mailto:me@example.net[me]", "me@example.net")]
+    #[test_case("See <<usage,Usage>>.", "usage")]
+    #[test_case("See xref:other.adoc#usage[].", "other.adoc#usage")]
+    #[test_case("Visit https://example.net/.", "https://example.net/")]
+    fn span(input: &str, raw: &str) {
+        let le = AsciiDocLinkExtractor();
+        let result = le.find_links(input);
+        assert_eq!(vec![raw], span_texts(input, &result));
+    }
+}
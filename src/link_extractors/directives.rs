@@ -0,0 +1,262 @@
+//! Inline directives suppressing the check of links, like `<!-- mlc-disable-next-line -->`.
+//! The directives are written in the comments of the markup, like `.. mlc-disable` in
+//! reStructuredText.
+
+use crate::link_extractors::link_extractor::LinkIssue;
+use crate::link_extractors::link_extractor::MarkupLink;
+use crate::markup::MarkupType;
+use regex::Regex;
+
+lazy_static! {
+    static ref DIRECTIVE_REGEX: Regex = Regex::new(
+        r"(?:^|[^`])(<!--|\{/\*|\.\.|//|/\*|#|%|;|--)[/!*#%;-]*[ \t]*(mlc-disable-next-line|mlc-disable-line|mlc-disable|mlc-enable)(?:[ \t]+(.*))?$"
+    )
+    .unwrap();
+}
+
+/// The parts of a document in which links are not checked
+pub struct Suppressions {
+    ranges: Vec<Range>,
+}
+
+/// Links from the start line and column up to the end are suppressed by the directive
+struct Range {
+    start: (usize, usize),
+    end: (usize, usize),
+    directive: String,
+}
+
+impl Suppressions {
+    /// Finds the directives in the comments of a document of the given markup type
+    #[must_use]
+    pub fn find(text: &str, markup_type: MarkupType) -> Self {
+        let mut ranges = vec![];
+        // The lines of notebook cells are not the lines of the file
+        if !text.contains("mlc-") || markup_type == MarkupType::Notebook {
+            return Self { ranges };
+        }
+        let markers = comment_markers(markup_type);
+        let fenced = matches!(markup_type, MarkupType::Markdown | MarkupType::Mdx);
+        let mut fence: Option<&str> = None;
+        let mut disabled: Option<((usize, usize), String)> = None;
+        for (idx, line) in text.lines().enumerate() {
+            let line_nr = idx + 1;
+            if fenced {
+                let trimmed = line.trim_start();
+                let marker = ["```", "~~~"]
+                    .iter()
+                    .copied()
+                    .find(|m| trimmed.starts_with(*m));
+                if let Some(marker) = marker {
+                    if fence.is_none() {
+                        fence = Some(marker);
+                    } else if fence == Some(marker) {
+                        fence = None;
+                    }
+                }
+                if fence.is_some() || marker.is_some() {
+                    continue;
+                }
+            }
+            let Some(caps) = DIRECTIVE_REGEX.captures(line) else {
+                continue;
+            };
+            let marker = caps.get(1).unwrap();
+            if !markers.is_empty() && !markers.contains(&marker.as_str()) {
+                continue;
+            }
+            let column = line[..marker.start()].chars().count() + 1;
+            let directive = &caps[2];
+            let mut description = format!("{directive} directive in line {line_nr}");
+            if let Some(reason) = caps.get(3).and_then(|r| reason(r.as_str())) {
+                description = format!("{description} ({reason})");
+            }
+            match directive {
+                "mlc-disable-line" => ranges.push(Range {
+                    start: (line_nr, 0),
+                    end: (line_nr + 1, 0),
+                    directive: description,
+                }),
+                "mlc-disable-next-line" => ranges.push(Range {
+                    start: (line_nr + 1, 0),
+                    end: (line_nr + 2, 0),
+                    directive: description,
+                }),
+                "mlc-disable" => {
+                    disabled = disabled.or(Some(((line_nr, column), description)));
+                }
+                _ => {
+                    if let Some((start, directive)) = disabled.take() {
+                        ranges.push(Range {
+                            start,
+                            end: (line_nr, column),
+                            directive,
+                        });
+                    }
+                }
+            }
+        }
+        // Without `mlc-enable` links are suppressed until the end of the document
+        if let Some((start, directive)) = disabled {
+            ranges.push(Range {
+                start,
+                end: (usize::MAX, 0),
+                directive,
+            });
+        }
+        Self { ranges }
+    }
+
+    /// Marks the links within suppressed ranges as suppressed
+    pub fn apply(&self, links: &mut [MarkupLink]) {
+        for link in links.iter_mut() {
+            let position = (link.line, link.column);
+            if let Some(range) = self
+                .ranges
+                .iter()
+                .find(|r| r.start <= position && position < r.end)
+            {
+                link.issue = Some(LinkIssue::Suppressed(range.directive.clone()));
+            }
+        }
+    }
+}
+
+/// The markers starting the comments which may contain directives.
+/// Any marker is accepted for source code and plain files.
+const fn comment_markers(markup_type: MarkupType) -> &'static [&'static str] {
+    match markup_type {
+        MarkupType::Markdown | MarkupType::Html | MarkupType::Svg => &["<!--"],
+        MarkupType::Mdx => &["<!--", "{/*"],
+        MarkupType::Rst => &[".."],
+        MarkupType::AsciiDoc => &["//"],
+        MarkupType::Org => &["#"],
+        MarkupType::Latex => &["%"],
+        MarkupType::Notebook | MarkupType::SourceCode | MarkupType::Plain => &[],
+    }
+}
+
+/// The text following a directive up to the end of its comment, like in
+/// `<!-- mlc-disable-next-line blocks crawlers -->`
+fn reason(rest: &str) -> Option<String> {
+    let end = ["-->", "*/"]
+        .iter()
+        .filter_map(|close| rest.find(close))
+        .min()
+        .unwrap_or(rest.len());
+    let reason = rest[..end].trim();
+    (!reason.is_empty()).then(|| reason.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::link_extractors::link_extractor::link;
+    use ntest::test_case;
+
+    fn suppressed(text: &str, markup_type: MarkupType, links: &[(usize, usize)]) -> Vec<bool> {
+        let mut links: Vec<MarkupLink> = links
+            .iter()
+            .map(|(l, c)| link("http://example.net", *l, *c))
+            .collect();
+        Suppressions::find(text, markup_type).apply(&mut links);
+        links.iter().map(|l| l.issue.is_some()).collect()
+    }
+
+    #[test]
+    fn disable_next_line() {
+        let text = "[a](x)\n<!-- mlc-disable-next-line -->\n[b](x) [c](x)\n[d](x)";
+        assert_eq!(
+            vec![false, false, true, true, false],
+            suppressed(
+                text,
+                MarkupType::Markdown,
+                &[(1, 1), (2, 1), (3, 1), (3, 8), (4, 1)]
+            )
+        );
+    }
+
+    #[test]
+    fn disable_line() {
+        let text = "[a](x) <!-- mlc-disable-line -->\n[b](x)";
+        assert_eq!(
+            vec![true, false],
+            suppressed(text, MarkupType::Markdown, &[(1, 1), (2, 1)])
+        );
+    }
+
+    #[test]
+    fn disable_and_enable() {
+        let text = "<a href='x'>\n<a href='x'> <!-- mlc-disable --><a href='x'>\n<a href='x'>\n<a href='x'><!-- mlc-enable --><a href='x'>";
+        assert_eq!(
+            vec![false, false, true, true, true, false],
+            suppressed(
+                text,
+                MarkupType::Html,
+                &[(1, 1), (2, 1), (2, 34), (3, 1), (4, 1), (4, 32)]
+            )
+        );
+    }
+
+    #[test]
+    fn disable_until_end() {
+        let text = "[a](x)\n<!-- mlc-disable -->\n<!-- mlc-disable -->\n[b](x)\n\n[c](x)";
+        assert_eq!(
+            vec![false, true, true],
+            suppressed(text, MarkupType::Markdown, &[(1, 1), (4, 1), (6, 1)])
+        );
+    }
+
+    #[test_case(".. mlc-disable-next-line", MarkupType::Rst, true)]
+    #[test_case("// mlc-disable-next-line", MarkupType::AsciiDoc, true)]
+    #[test_case("# mlc-disable-next-line", MarkupType::Org, true)]
+    #[test_case("% mlc-disable-next-line", MarkupType::Latex, true)]
+    #[test_case("{/* mlc-disable-next-line */}", MarkupType::Mdx, true)]
+    #[test_case("# mlc-disable-next-line", MarkupType::Plain, true)]
+    #[test_case("  /// mlc-disable-next-line", MarkupType::SourceCode, true)]
+    #[test_case("// mlc-disable-next-line", MarkupType::Markdown, false)]
+    #[test_case("<!-- mlc-disable-next-line -->", MarkupType::Rst, false)]
+    #[test_case("`<!-- mlc-disable-next-line -->`", MarkupType::Markdown, false)]
+    #[test_case("<!-- mlc-disable-next-lines -->", MarkupType::Markdown, false)]
+    #[test_case("<!-- mlc-disable-next-line -->", MarkupType::Notebook, false)]
+    fn comment_syntax(directive: &str, markup_type: MarkupType, suppress: bool) {
+        let text = format!("{directive}\nhttp://example.net");
+        assert_eq!(vec![suppress], suppressed(&text, markup_type, &[(2, 1)]));
+    }
+
+    #[test]
+    fn fenced_code() {
+        let text = "```md\n<!-- mlc-disable-next-line -->\n```\n[a](x)";
+        assert_eq!(
+            vec![false],
+            suppressed(text, MarkupType::Markdown, &[(4, 1)])
+        );
+    }
+
+    #[test_case(
+        "<!-- mlc-disable-next-line -->",
+        "mlc-disable-next-line directive in line 1"
+    )]
+    #[test_case(
+        "<!-- mlc-disable-next-line blocks crawlers -->",
+        "mlc-disable-next-line directive in line 1 (blocks crawlers)"
+    )]
+    #[test_case(
+        ".. mlc-disable-next-line moved",
+        "mlc-disable-next-line directive in line 1 (moved)"
+    )]
+    fn description(directive: &str, description: &str) {
+        let text = format!("{directive}\nhttp://example.net");
+        let mut links = vec![link("http://example.net", 2, 1)];
+        let markup_type = if directive.starts_with("..") {
+            MarkupType::Rst
+        } else {
+            MarkupType::Markdown
+        };
+        Suppressions::find(&text, markup_type).apply(&mut links);
+        assert_eq!(
+            Some(LinkIssue::Suppressed(description.to_string())),
+            links[0].issue
+        );
+    }
+}
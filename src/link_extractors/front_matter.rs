@@ -0,0 +1,263 @@
+//! Front matter of static site generators like Jekyll, Hugo and Docusaurus.
+//! It is written in YAML between `---` lines, or in TOML between `+++` lines,
+//! at the very beginning of a markup file.
+
+use std::ops::Range;
+
+/// The front matter block at the beginning of a file
+#[derive(Debug, PartialEq, Eq)]
+pub struct FrontMatter {
+    /// The byte range of the content between the delimiter lines
+    body: Range<usize>,
+    /// The byte index behind the closing delimiter line
+    end: usize,
+    toml: bool,
+}
+
+impl FrontMatter {
+    /// Finds the front matter at the beginning of `text`, if it is closed by a delimiter line
+    #[must_use]
+    pub fn find(text: &str) -> Option<Self> {
+        let first_line_len = text.find('\n').map_or(text.len(), |idx| idx + 1);
+        let toml = match text[..first_line_len].trim_end() {
+            "---" => false,
+            "+++" => true,
+            _ => return None,
+        };
+        let mut line_start = first_line_len;
+        while line_start < text.len() {
+            let line_end = text[line_start..]
+                .find('\n')
+                .map_or(text.len(), |idx| line_start + idx + 1);
+            let closing = match text[line_start..line_end].trim_end() {
+                "+++" => toml,
+                "---" | "..." => !toml,
+                _ => false,
+            };
+            if closing {
+                return Some(Self {
+                    body: first_line_len..line_start,
+                    end: line_end,
+                    toml,
+                });
+            }
+            line_start = line_end;
+        }
+        None
+    }
+
+    /// The byte index behind the closing delimiter line
+    #[must_use]
+    pub const fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Replaces the front matter with white-space, keeping the byte indices and lines of the text
+    #[must_use]
+    pub fn mask(&self, text: &str) -> String {
+        let masked: String = text[..self.end]
+            .chars()
+            .map(|c| {
+                if c == '\n' {
+                    "\n".to_string()
+                } else {
+                    " ".repeat(c.len_utf8())
+                }
+            })
+            .collect();
+        masked + &text[self.end..]
+    }
+
+    /// The values of the top level `keys` with their byte index.
+    /// Values may be single strings or lists of strings.
+    #[must_use]
+    pub fn values(&self, text: &str, keys: &[String]) -> Vec<(usize, String)> {
+        let separator = if self.toml { '=' } else { ':' };
+        let body = &text[self.body.clone()];
+        let mut result = Vec::new();
+        let mut line_start = 0;
+        while line_start < body.len() {
+            let line_end = body[line_start..]
+                .find('\n')
+                .map_or(body.len(), |idx| line_start + idx + 1);
+            let line = &body[line_start..line_end];
+            let Some((key, _)) = line.split_once(separator) else {
+                line_start = line_end;
+                continue;
+            };
+            let key = key.trim_end().trim_matches(['"', '\'']);
+            if line.starts_with(char::is_whitespace) || !keys.iter().any(|k| k == key) {
+                line_start = line_end;
+                continue;
+            }
+            let value_start = line_start + line.find(separator).unwrap_or_default() + 1;
+            let value = &body[value_start..];
+            let first_line = &body[value_start..line_end];
+            let offset = self.body.start;
+            line_start = line_end;
+            if first_line.trim_start().starts_with('[') {
+                let (values, end) = list(value);
+                result.extend(
+                    values
+                        .into_iter()
+                        .map(|(idx, v)| (offset + value_start + idx, v)),
+                );
+                line_start = value_start + end;
+            } else if first_line.trim().is_empty() && !self.toml {
+                let (values, end) = block_list(&body[line_end..]);
+                result.extend(
+                    values
+                        .into_iter()
+                        .map(|(idx, v)| (offset + line_end + idx, v)),
+                );
+                line_start = line_end + end;
+            } else if let Some((idx, v)) = scalar(first_line, &['\n']) {
+                result.push((offset + value_start + idx, v));
+            }
+        }
+        result
+    }
+}
+
+/// A single value like `"x"`, `'x'` or `x`, ending at one of the `terminators`
+/// or at a comment. Returns the byte index of the value.
+fn scalar(text: &str, terminators: &[char]) -> Option<(usize, String)> {
+    let start = text.len() - text.trim_start_matches([' ', '\t']).len();
+    let rest = &text[start..];
+    let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'');
+    let (idx, value) = if let Some(quote) = quote {
+        let end = rest[1..].find(quote).map_or(rest.len(), |end| end + 1);
+        (start + 1, &rest[1..end])
+    } else {
+        let end = rest.find(terminators).unwrap_or(rest.len());
+        let value = &rest[..end];
+        let value = value.find(" #").map_or(value, |comment| &value[..comment]);
+        (start, value.trim_end())
+    };
+    Some((idx, value.to_string())).filter(|(_, value)| !value.is_empty())
+}
+
+/// The values of a list like `["a", "b"]`, which may span several lines,
+/// and the byte index behind its closing bracket
+fn list(text: &str) -> (Vec<(usize, String)>, usize) {
+    let mut result = Vec::new();
+    let mut idx = text.find('[').map_or(text.len(), |idx| idx + 1);
+    while idx < text.len() {
+        let item_start = text.len() - text[idx..].trim_start().len();
+        if text[item_start..].starts_with(']') {
+            return (result, item_start + 1);
+        }
+        let item = &text[item_start..];
+        let item_len = if item.starts_with(['"', '\'']) {
+            let quote = &item[..1];
+            item[1..].find(quote).map_or(item.len(), |end| end + 2)
+        } else {
+            item.find([',', ']', '\n']).unwrap_or(item.len())
+        };
+        if let Some((value_idx, value)) = scalar(&item[..item_len], &[]) {
+            result.push((item_start + value_idx, value));
+        }
+        idx = item_start + item_len;
+        idx = text.len() - text[idx..].trim_start().trim_start_matches(',').len();
+    }
+    (result, text.len())
+}
+
+/// The values of a YAML block sequence with one `- value` item per line,
+/// and the byte index behind its last item
+fn block_list(text: &str) -> (Vec<(usize, String)>, usize) {
+    let mut result = Vec::new();
+    let mut line_start = 0;
+    while line_start < text.len() {
+        let line_end = text[line_start..]
+            .find('\n')
+            .map_or(text.len(), |idx| line_start + idx + 1);
+        let line = &text[line_start..line_end];
+        let item = line.trim_start();
+        if let Some(value) = item.strip_prefix('-') {
+            let value_start = line_start + line.len() - value.len();
+            if let Some((idx, v)) = scalar(value, &['\n']) {
+                result.push((value_start + idx, v));
+            }
+        } else if !item.is_empty() {
+            break;
+        }
+        line_start = line_end;
+    }
+    (result, line_start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntest::test_case;
+
+    fn values(text: &str, key: &str) -> Vec<(usize, String)> {
+        FrontMatter::find(text)
+            .unwrap()
+            .values(text, &[key.to_string()])
+    }
+
+    #[test_case("---\ntitle: x\n---\n# Title", 17)]
+    #[test_case("---\r\ntitle: x\r\n...\r\n# Title", 20)]
+    #[test_case("+++\ntitle = \"x\"\n+++\n", 20)]
+    #[test_case("---\n---", 7)]
+    fn find(text: &str, end: usize) {
+        assert_eq!(end, FrontMatter::find(text).unwrap().end);
+    }
+
+    #[test_case("# Title\n---\na: b\n---\n")]
+    #[test_case("---\ntitle: x\n")]
+    #[test_case("+++\ntitle = \"x\"\n---\n")]
+    #[test_case("")]
+    fn no_front_matter(text: &str) {
+        assert_eq!(None, FrontMatter::find(text));
+    }
+
+    #[test]
+    fn mask() {
+        let text = "---\ntitle: ü\n---\n# Title";
+        let masked = FrontMatter::find(text).unwrap().mask(text);
+        assert_eq!("   \n         \n   \n# Title", masked);
+    }
+
+    #[test_case("---\nimage: ./img.png\n---\n", 11, "./img.png")]
+    #[test_case("---\nimage:  \"./img.png\" # logo\n---\n", 13, "./img.png")]
+    #[test_case("---\nimage: ./img.png # logo\n---\n", 11, "./img.png")]
+    #[test_case("---\n\"image\": './img.png'\n---\n", 14, "./img.png")]
+    #[test_case("+++\nimage = \"./img.png\"\n+++\n", 13, "./img.png")]
+    #[test_case("---\ntitle: a\nimage: ./img.png\ntags: b\n---\n", 20, "./img.png")]
+    fn single_value(text: &str, idx: usize, value: &str) {
+        assert_eq!(vec![(idx, value.to_string())], values(text, "image"));
+    }
+
+    #[test]
+    fn block_list_values() {
+        let text = "---\nredirect_from:\n  - /old/\n  - \"/older/\"\n\ntitle: x\n---\n";
+        assert_eq!(
+            vec![(23, "/old/".to_string()), (34, "/older/".to_string())],
+            values(text, "redirect_from")
+        );
+    }
+
+    #[test]
+    fn flow_list_values() {
+        let text = "+++\naliases = [\n  \"/old/\",\n  '/older/' ]\ntitle = \"x\"\n+++\n";
+        assert_eq!(
+            vec![(19, "/old/".to_string()), (30, "/older/".to_string())],
+            values(text, "aliases")
+        );
+        let text = "---\naliases: [/old/, /older/]\n---\n";
+        assert_eq!(
+            vec![(14, "/old/".to_string()), (21, "/older/".to_string())],
+            values(text, "aliases")
+        );
+    }
+
+    #[test_case("---\nimages:\n  image: ./img.png\n---\n")]
+    #[test_case("---\nimage:\n---\n")]
+    #[test_case("---\ndescription: the image: x\n---\n")]
+    fn no_values(text: &str) {
+        assert!(values(text, "image").is_empty());
+    }
+}
@@ -1,116 +1,139 @@
+use crate::link_extractors::html_tokenizer::decode_character_references;
+use crate::link_extractors::html_tokenizer::start_tags;
+use crate::link_extractors::link_extractor::line_column_from_idx;
+use crate::link_extractors::link_extractor::trimmed_range;
 use crate::link_extractors::link_extractor::LinkExtractor;
+use crate::link_extractors::link_extractor::LinkKind;
 use crate::link_extractors::link_extractor::MarkupLink;
+use crate::link_extractors::link_extractor::Span;
 use crate::link_validator::link_type::get_link_type;
 use crate::link_validator::link_type::LinkType;
+use std::ops::Range;
+
 pub struct HtmlLinkExtractor();
 
-#[derive(Clone, Copy, Debug)]
-enum ParserState {
-    Text,
-    Comment,
-    Anchor,
-    EqualSign,
-    Link,
-}
+/// The attributes of elements which link to other resources
+const LINK_ATTRIBUTES: [(&str, &str); 17] = [
+    ("a", "href"),
+    ("area", "href"),
+    ("link", "href"),
+    ("img", "src"),
+    ("img", "srcset"),
+    ("script", "src"),
+    ("iframe", "src"),
+    ("embed", "src"),
+    ("audio", "src"),
+    ("track", "src"),
+    ("source", "src"),
+    ("source", "srcset"),
+    ("video", "src"),
+    ("video", "poster"),
+    ("form", "action"),
+    ("object", "data"),
+    ("input", "src"),
+];
 
 impl LinkExtractor for HtmlLinkExtractor {
     fn find_links(&self, text: &str) -> Vec<MarkupLink> {
         let mut result: Vec<MarkupLink> = Vec::new();
-        let mut state: ParserState = ParserState::Text;
-        let mut link_column = 0;
-        let mut link_line = 0;
-        for (line, line_str) in text.lines().enumerate() {
-            let line_chars: Vec<char> = line_str.chars().collect();
-            let mut column: usize = 0;
-            while line_chars.get(column).is_some() {
-                match state {
-                    ParserState::Comment => {
-                        if line_chars.get(column) == Some(&'-')
-                            && line_chars.get(column + 1) == Some(&'-')
-                            && line_chars.get(column + 2) == Some(&'>')
-                        {
-                            column += 2;
-                            state = ParserState::Text;
-                        }
-                    }
-                    ParserState::Text => {
-                        link_column = column;
-                        link_line = line;
-                        if line_chars.get(column) == Some(&'<')
-                            && line_chars.get(column + 1) == Some(&'!')
-                            && line_chars.get(column + 2) == Some(&'-')
-                            && line_chars.get(column + 3) == Some(&'-')
-                        {
-                            column += 3;
-                            state = ParserState::Comment;
-                        } else if line_chars.get(column) == Some(&'<')
-                            && line_chars.get(column + 1) == Some(&'a')
-                        {
-                            column += 1;
-                            state = ParserState::Anchor;
-                        }
-                    }
-                    ParserState::Anchor => {
-                        if line_chars.get(column) == Some(&'h')
-                            && line_chars.get(column + 1) == Some(&'r')
-                            && line_chars.get(column + 2) == Some(&'e')
-                            && line_chars.get(column + 3) == Some(&'f')
-                        {
-                            column += 3;
-                            state = ParserState::EqualSign;
-                        }
-                    }
-                    ParserState::EqualSign => {
-                        match line_chars.get(column) {
-                            Some(x) if x.is_whitespace() => {}
-                            Some(&'=') => state = ParserState::Link,
-                            Some(_) => state = ParserState::Anchor,
-                            None => {}
-                        };
-                    }
-                    ParserState::Link => {
-                        match line_chars.get(column) {
-                            Some(x) if !x.is_whitespace() && x != &'"' => {
-                                let start_col = column;
-                                while line_chars.get(column).is_some()
-                                    && !line_chars[column].is_whitespace()
-                                    && line_chars[column] != '"'
-                                {
-                                    column += 1;
-                                }
-                                while let Some(c) = line_chars.get(column) {
-                                    if c == &'"' {
-                                        break;
-                                    }
-                                    column += 1;
-                                }
-                                let mut link =
-                                    (line_chars[start_col..column]).iter().collect::<String>();
-                                if get_link_type(&link) == LinkType::FileSystem {
-                                    link = url_escape::decode(link.as_str()).to_string();
-                                };
-                                result.push(MarkupLink {
-                                    column: link_column + 1,
-                                    line: link_line + 1,
-                                    target: link.to_string(),
-                                    source: String::new(),
-                                });
-                                state = ParserState::Text;
-                            }
-                            Some(_) | None => {}
-                        };
+        let tags = start_tags(text);
+        // Only the first base element with an `href` is used by browsers
+        let base = tags
+            .iter()
+            .filter(|tag| tag.name == "base")
+            .find_map(|tag| tag.attributes.iter().find(|a| a.name == "href"))
+            .map(|a| a.value.trim().to_string())
+            .filter(|href| !href.is_empty());
+        for tag in tags {
+            let (line, column) = line_column_from_idx(text, tag.start);
+            let attribute_value = |name: &str| {
+                tag.attributes
+                    .iter()
+                    .find(|a| a.name == name)
+                    .map(|a| a.value.trim().to_string())
+                    .filter(|v| !v.is_empty())
+            };
+            for attribute in &tag.attributes {
+                if !LINK_ATTRIBUTES.contains(&(tag.name.as_str(), attribute.name.as_str())) {
+                    continue;
+                }
+                let raw_value = &text[attribute.value_range.clone()];
+                let ranges = if attribute.name == "srcset" {
+                    srcset_urls(raw_value)
+                } else {
+                    vec![trimmed_range(raw_value, 0..raw_value.len())]
+                };
+                for range in ranges.into_iter().filter(|r| !r.is_empty()) {
+                    let mut target = decode_character_references(&raw_value[range.clone()]);
+                    if get_link_type(&target) == LinkType::FileSystem {
+                        target = url_escape::decode(&target).to_string();
                     }
+                    result.push(MarkupLink {
+                        column,
+                        line,
+                        target,
+                        base: base.clone(),
+                        kind: attribute_kind(&tag.name, &attribute.name),
+                        text: attribute_value("alt"),
+                        title: attribute_value("title"),
+                        attribute: Some(attribute.name.clone()),
+                        span: Span::new(
+                            text,
+                            attribute.value_range.start + range.start
+                                ..attribute.value_range.start + range.end,
+                        ),
+                        ..MarkupLink::default()
+                    });
                 }
-                column += 1;
             }
         }
         result
     }
 }
 
+/// Images are embedded by the `src` and `srcset` of `<img>`, the `srcset` of `<source>`,
+/// the `poster` of `<video>` and the `href` of SVG `<image>`. Other attributes are HTML links.
+pub fn attribute_kind(element: &str, attribute: &str) -> LinkKind {
+    match (element, attribute) {
+        ("img", "src" | "srcset")
+        | ("source", "srcset")
+        | ("video", "poster")
+        | ("image", "href" | "xlink:href") => LinkKind::Image,
+        _ => LinkKind::Html,
+    }
+}
+
+/// The byte ranges of the image candidate URLs of a `srcset` attribute like `a.png 1x, b.png 2x`
+fn srcset_urls(srcset: &str) -> Vec<Range<usize>> {
+    let mut result = Vec::new();
+    let mut rest = srcset;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_ascii_whitespace() || c == ',');
+        if rest.is_empty() {
+            return result;
+        }
+        let start = srcset.len() - rest.len();
+        let url_end = rest
+            .find(|c: char| c.is_ascii_whitespace())
+            .unwrap_or(rest.len());
+        let url = &rest[..url_end];
+        if url.ends_with(',') {
+            result.push(start..start + url.trim_end_matches(',').len());
+            rest = &rest[url_end..];
+            continue;
+        }
+        result.push(start..start + url_end);
+        // Skip the descriptors like `2x` or `100w`
+        rest = &rest[url_end..];
+        rest = rest.find(',').map_or("", |end| &rest[end + 1..]);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::link_extractors::link_extractor::link;
+    use crate::link_extractors::link_extractor::span_texts;
     use ntest::test_case;
 
     #[test]
@@ -132,27 +155,33 @@ mod tests {
     #[test]
     fn space() {
         let le = HtmlLinkExtractor();
-        let result = le.find_links("blah <a href=\"some file.html\">foo</a>.");
+        let input = "blah <a href=\"some file.html\">foo</a>.";
+        let result = le.find_links(input);
         let expected = MarkupLink {
             target: "some file.html".to_string(),
             line: 1,
             column: 6,
-            source: "".to_string(),
+            kind: LinkKind::Html,
+            attribute: Some("href".to_string()),
+            ..MarkupLink::default()
         };
-        assert_eq!(vec![expected], result);
+        assert_eq!(vec![expected.spanned(input)], result);
     }
 
     #[test]
     fn url_encoded_path() {
         let le = HtmlLinkExtractor();
-        let result = le.find_links("blah <a href=\"some%20file.html\">foo</a>.");
+        let input = "blah <a href=\"some%20file.html\">foo</a>.";
+        let result = le.find_links(input);
         let expected = MarkupLink {
             target: "some file.html".to_string(),
             line: 1,
             column: 6,
-            source: "".to_string(),
+            kind: LinkKind::Html,
+            attribute: Some("href".to_string()),
+            ..MarkupLink::default()
         };
-        assert_eq!(vec![expected], result);
+        assert_eq!(vec![expected.spanned_as(input, "some%20file.html")], result);
     }
 
     #[test_case("<a href=\"https://www.w3schools.com\">Visit W3Schools.com!</a>", 1, 1)]
@@ -178,8 +207,202 @@ mod tests {
             target: "https://www.w3schools.com".to_string(),
             line,
             column,
-            source: "".to_string(),
+            kind: LinkKind::Html,
+            attribute: Some("href".to_string()),
+            ..MarkupLink::default()
         };
-        assert_eq!(vec![expected], result);
+        assert_eq!(vec![expected.spanned(input)], result);
+    }
+
+    fn href_link(target: &str, line: usize, column: usize) -> MarkupLink {
+        MarkupLink {
+            kind: LinkKind::Html,
+            attribute: Some("href".to_string()),
+            ..link(target, line, column)
+        }
+    }
+
+    #[test_case("<img src=\"x.png\" alt=\"X\">", "src", true, test_name = "img_src")]
+    #[test_case(
+        "<link rel=\"stylesheet\" href=\"x.png\">",
+        "href",
+        false,
+        test_name = "link_href"
+    )]
+    #[test_case(
+        "<script src=\"x.png\"></script>",
+        "src",
+        false,
+        test_name = "script_src"
+    )]
+    #[test_case(
+        "<iframe src=\"x.png\"></iframe>",
+        "src",
+        false,
+        test_name = "iframe_src"
+    )]
+    #[test_case(
+        "<source srcset=\"x.png\">",
+        "srcset",
+        true,
+        test_name = "source_srcset"
+    )]
+    #[test_case(
+        "<video poster=\"x.png\"></video>",
+        "poster",
+        true,
+        test_name = "video_poster"
+    )]
+    #[test_case(
+        "<form action=\"x.png\"></form>",
+        "action",
+        false,
+        test_name = "form_action"
+    )]
+    #[test_case(
+        "<area shape=\"rect\" href=\"x.png\">",
+        "href",
+        false,
+        test_name = "area_href"
+    )]
+    #[test_case("<a href='x.png'>X</a>", "href", false, test_name = "single_quoted")]
+    #[test_case("<a href=x.png>X</a>", "href", false, test_name = "unquoted")]
+    #[test_case("<A HREF=\"x.png\">X</A>", "href", false, test_name = "uppercase")]
+    #[test_case(
+        "<a href=\" x.png \">X</a>",
+        "href",
+        false,
+        test_name = "surrounding_space"
+    )]
+    #[test_case(
+        "<a title=\"a > b\" href=\"x.png\">X</a>",
+        "href",
+        false,
+        test_name = "greater_than_in_value"
+    )]
+    fn link_elements(input: &str, attribute: &str, image: bool) {
+        let le = HtmlLinkExtractor();
+        let result: Vec<(String, LinkKind, Option<String>)> = le
+            .find_links(input)
+            .into_iter()
+            .map(|l| (l.target, l.kind, l.attribute))
+            .collect();
+        let kind = if image {
+            LinkKind::Image
+        } else {
+            LinkKind::Html
+        };
+        assert_eq!(
+            vec![("x.png".to_string(), kind, Some(attribute.to_string()))],
+            result
+        );
+    }
+
+    #[test_case(
+        "<img src=\"x.png\" alt=\" Logo \" title=\"The logo\">",
+        "Logo",
+        "The logo"
+    )]
+    #[test_case("<a href=\"x.png\" title=\"a > b\">X</a>", "", "a > b")]
+    #[test_case("<img src=\"x.png\" alt=\"\">", "", "")]
+    fn text_and_title(input: &str, text: &str, title: &str) {
+        let le = HtmlLinkExtractor();
+        let result = le.find_links(input);
+        let some = |s: &str| Some(s.to_string()).filter(|s| !s.is_empty());
+        assert_eq!(some(text), result[0].text);
+        assert_eq!(some(title), result[0].title);
+    }
+
+    #[test]
+    fn srcset() {
+        let le = HtmlLinkExtractor();
+        let input = "<img src=\"a.png\" srcset=\"b.png 2x,c.png 3x, d%20e.png\">";
+        let result = le.find_links(input);
+        let expected = vec!["a.png", "b.png", "c.png", "d e.png"];
+        assert_eq!(
+            expected,
+            result.iter().map(|l| l.target.as_str()).collect::<Vec<_>>()
+        );
+        assert!(result.iter().all(|l| l.kind == LinkKind::Image));
+    }
+
+    #[test_case("<a name=\"x\">X</a>", test_name = "no_href")]
+    #[test_case("<a href=\"\">X</a>", test_name = "empty_href")]
+    #[test_case("<div src=\"x.png\"></div>", test_name = "no_link_element")]
+    #[test_case(
+        "<script>let a = '<a href=\"x.png\">';</script>",
+        test_name = "script_text"
+    )]
+    #[test_case(
+        "<textarea><img src=\"x.png\"></textarea>",
+        test_name = "textarea_text"
+    )]
+    fn ignored(input: &str) {
+        let le = HtmlLinkExtractor();
+        let result = le.find_links(input);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn escaped_query() {
+        let le = HtmlLinkExtractor();
+        let input = "<a href=\"https://example.net/?a=1&amp;b=2\">X</a>";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![href_link("https://example.net/?a=1&b=2", 1, 1)
+                .spanned_as(input, "https://example.net/?a=1&amp;b=2")],
+            result
+        );
+    }
+
+    #[test]
+    fn positions() {
+        let le = HtmlLinkExtractor();
+        let input = "<p>Ünïcode <img src=\"a.png\"></p>\r\n  <script src=\"b.js\"></script>";
+        let result = le.find_links(input);
+        let positions: Vec<(&str, usize, usize)> = result
+            .iter()
+            .map(|l| (l.target.as_str(), l.line, l.column))
+            .collect();
+        assert_eq!(vec![("a.png", 1, 12), ("b.js", 2, 3)], positions);
+    }
+
+    #[test]
+    fn base() {
+        let le = HtmlLinkExtractor();
+        let input = "<a href=\"a.html\"></a><base target=\"_blank\"><base href=\" docs/ \"><base href=\"other/\">";
+        let result = le.find_links(input);
+        assert_eq!(1, result.len());
+        assert_eq!(Some("docs/".to_string()), result[0].base);
+    }
+
+    #[test]
+    fn no_base() {
+        let le = HtmlLinkExtractor();
+        let result = le.find_links("<base href=\"\"><a href=\"a.html\"></a>");
+        assert_eq!(None, result[0].base);
+    }
+
+    #[test_case("<a href=\"a.html\">A</a>", "a.html")]
+    #[test_case("<a href=' a.html '>A</a>", "a.html")]
+    #[test_case("<a href=a.html>A</a>", "a.html")]
+    #[test_case("<a href=\"a&amp;b.html\">A</a>", "a&amp;b.html")]
+    #[test_case("<a href=\"a%20b.html\">A</a>", "a%20b.html")]
+    fn span(input: &str, raw: &str) {
+        let le = HtmlLinkExtractor();
+        let result = le.find_links(input);
+        assert_eq!(vec![raw], span_texts(input, &result));
+    }
+
+    #[test]
+    fn srcset_spans() {
+        let le = HtmlLinkExtractor();
+        let input = "<p>😀</p>\n<img srcset=\"a.png 2x, b%20c.png\">";
+        let result = le.find_links(input);
+        assert_eq!(vec!["a.png", "b%20c.png"], span_texts(input, &result));
+        assert_eq!(
+            (2, 14),
+            (result[0].span.start.line, result[0].span.start.char_column)
+        );
     }
 }
@@ -0,0 +1,461 @@
+//! A tokenizer for the start tags of HTML documents,
+//! following the tokenization rules of the HTML living standard for tags, attributes, character references,
+//! comments and raw text elements like `<script>`.
+
+use crate::link_extractors::html_entities::NAMED_CHARACTER_REFERENCES;
+use std::ops::Range;
+
+/// The length of the longest name of a named character reference, like `CounterClockwiseContourIntegral;`
+const MAX_NAME_LEN: usize = 32;
+
+/// A start tag like `<a href="x">`
+#[derive(Debug, PartialEq, Eq)]
+pub struct StartTag {
+    /// The lowercase tag name
+    pub name: String,
+    /// The byte index of the opening `<`
+    pub start: usize,
+    pub attributes: Vec<Attribute>,
+}
+
+/// An attribute of a start tag
+#[derive(Debug, PartialEq, Eq)]
+pub struct Attribute {
+    /// The lowercase attribute name
+    pub name: String,
+    /// The value with decoded character references like `&amp;`
+    pub value: String,
+    /// The byte index of the first character of the attribute name
+    pub start: usize,
+    /// The byte range of the raw value without quotes
+    pub value_range: Range<usize>,
+}
+
+/// Elements whose content is text, which may contain `<` characters without starting a tag
+const RAW_TEXT_ELEMENTS: [&str; 8] = [
+    "script", "style", "textarea", "title", "xmp", "iframe", "noembed", "noframes",
+];
+
+/// Finds all start tags of an HTML document. Comments, end tags, doctypes,
+/// processing instructions and the content of raw text elements are skipped.
+#[must_use]
+pub fn start_tags(text: &str) -> Vec<StartTag> {
+    let bytes = text.as_bytes();
+    let mut result = Vec::new();
+    let mut idx = 0;
+    while let Some(offset) = text[idx..].find('<') {
+        let start = idx + offset;
+        let rest = &text[start..];
+        let is_tag_name_start = |idx: usize| bytes.get(idx).is_some_and(u8::is_ascii_alphabetic);
+        if rest.starts_with("<!--") {
+            idx = comment_end(text, start + 4);
+        } else if rest.starts_with("<![CDATA[") {
+            idx = find_end(text, start + 9, "]]>");
+        } else if rest.starts_with("</") && is_tag_name_start(start + 2) {
+            // End tags are tokenized like start tags, so quoted `>` of attributes do not end them
+            idx = start_tag(text, start + 1).1;
+        } else if rest.starts_with("<!") || rest.starts_with("<?") || rest.starts_with("</") {
+            idx = find_end(text, start + 2, ">");
+        } else if is_tag_name_start(start + 1) {
+            let (tag, end) = start_tag(text, start);
+            idx = end;
+            if tag.name == "plaintext" {
+                idx = text.len();
+            } else if RAW_TEXT_ELEMENTS.contains(&tag.name.as_str()) {
+                idx = raw_text_end(text, idx, &tag.name);
+            }
+            result.push(tag);
+        } else {
+            idx = start + 1;
+        }
+    }
+    result
+}
+
+/// The byte index behind the next `terminator` at or after `idx`, or the end of the text
+fn find_end(text: &str, idx: usize, terminator: &str) -> usize {
+    let idx = idx.min(text.len());
+    text[idx..]
+        .find(terminator)
+        .map_or(text.len(), |end| idx + end + terminator.len())
+}
+
+/// The byte index behind the comment whose content starts at `idx`.
+/// Comments end with `-->` or `--!>`, and the empty comments `<!-->` and `<!--->` right away.
+fn comment_end(text: &str, idx: usize) -> usize {
+    let content = &text[idx..];
+    if content.starts_with('>') {
+        return idx + 1;
+    }
+    if content.starts_with("->") {
+        return idx + 2;
+    }
+    find_end(text, idx, "-->").min(find_end(text, idx, "--!>"))
+}
+
+/// The byte index of the end tag `</name` closing the raw text starting at `idx`.
+/// The name must be followed by white-space, `/` or `>` to end the raw text.
+fn raw_text_end(text: &str, idx: usize, name: &str) -> usize {
+    let mut search = idx;
+    while let Some(offset) = text[search..].find("</") {
+        let end_tag = search + offset;
+        let candidate = &text.as_bytes()[end_tag + 2..];
+        if candidate.len() >= name.len()
+            && candidate[..name.len()].eq_ignore_ascii_case(name.as_bytes())
+            && !candidate
+                .get(name.len())
+                .is_some_and(|c| !c.is_ascii_whitespace() && *c != b'/' && *c != b'>')
+        {
+            return end_tag;
+        }
+        search = end_tag + 2;
+    }
+    text.len()
+}
+
+/// The byte index of the first character at or after `idx` which is no white-space
+fn skip_whitespace(text: &str, idx: usize) -> usize {
+    text.len()
+        - text[idx..]
+            .trim_start_matches(|c: char| c.is_ascii_whitespace())
+            .len()
+}
+
+/// Tokenizes the start tag at byte index `start`,
+/// and returns it with the byte index behind its closing `>`.
+fn start_tag(text: &str, start: usize) -> (StartTag, usize) {
+    let is_name_end = |c: char| c.is_ascii_whitespace() || c == '/' || c == '>';
+    let mut idx = start + 1;
+    let name_len = text[idx..].find(is_name_end).unwrap_or(text.len() - idx);
+    let name = text[idx..idx + name_len].to_ascii_lowercase();
+    idx += name_len;
+
+    let mut attributes: Vec<Attribute> = Vec::new();
+    loop {
+        // Before attribute name
+        idx = text.len()
+            - text[idx..]
+                .trim_start_matches(|c: char| c.is_ascii_whitespace() || c == '/')
+                .len();
+        if idx >= text.len() {
+            break;
+        }
+        if text[idx..].starts_with('>') {
+            idx += 1;
+            break;
+        }
+        // Attribute name, which may start with `=`
+        let attribute_start = idx;
+        let first_len = text[idx..].chars().next().map_or(1, char::len_utf8);
+        let name_len = text[idx + first_len..]
+            .find(|c: char| is_name_end(c) || c == '=')
+            .map_or(text.len() - idx, |len| len + first_len);
+        let attribute_name = text[idx..idx + name_len].to_ascii_lowercase();
+        idx += name_len;
+        // Attribute value
+        let mut value = String::new();
+        let mut value_range = idx..idx;
+        let after_name = skip_whitespace(text, idx);
+        if text[after_name..].starts_with('=') {
+            idx = skip_whitespace(text, after_name + 1);
+            let quote = text[idx..]
+                .chars()
+                .next()
+                .filter(|c| *c == '"' || *c == '\'');
+            let end = if let Some(quote) = quote {
+                let value_start = idx + 1;
+                let value_end = text[value_start..]
+                    .find(quote)
+                    .map_or(text.len(), |end| value_start + end);
+                value_range = value_start..value_end;
+                (value_end + 1).min(text.len())
+            } else {
+                let value_end = text[idx..]
+                    .find(|c: char| c.is_ascii_whitespace() || c == '>')
+                    .map_or(text.len(), |end| idx + end);
+                value_range = idx..value_end;
+                value_end
+            };
+            value = decode_character_references(&text[value_range.clone()]);
+            idx = end;
+        }
+        // Duplicate attributes are ignored
+        if attributes.iter().all(|a| a.name != attribute_name) {
+            attributes.push(Attribute {
+                name: attribute_name,
+                value,
+                start: attribute_start,
+                value_range,
+            });
+        }
+    }
+    (
+        StartTag {
+            name,
+            start,
+            attributes,
+        },
+        idx,
+    )
+}
+
+/// Decodes the character references of an attribute value the way the HTML living standard does.
+/// Named references like `&eacute;` are looked up in the full table of the standard,
+/// and the legacy ones without `;` like `&copy` are only decoded if no `=` or alphanumeric follows.
+/// Numeric references like `&#233;` or `&#xE9;` may omit the `;` as well.
+#[must_use]
+pub fn decode_character_references(value: &str) -> String {
+    if !value.contains('&') {
+        return value.to_string();
+    }
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(idx) = rest.find('&') {
+        result.push_str(&rest[..idx]);
+        rest = &rest[idx + 1..];
+        if let Some((c, len)) = numeric_character_reference(rest) {
+            result.push(c);
+            rest = &rest[len..];
+        } else if let Some((decoded, len)) = named_character_reference(rest) {
+            result.push_str(decoded);
+            rest = &rest[len..];
+        } else {
+            result.push('&');
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// The characters of the longest named reference at the start of `rest`, which follows a `&`,
+/// and the length of its name
+fn named_character_reference(rest: &str) -> Option<(&'static str, usize)> {
+    let max_len = rest.len().min(MAX_NAME_LEN);
+    (2..=max_len)
+        .rev()
+        .filter(|len| rest.is_char_boundary(*len))
+        .find_map(|len| {
+            NAMED_CHARACTER_REFERENCES
+                .binary_search_by_key(&&rest[..len], |(name, _)| name)
+                .ok()
+                .map(|idx| (NAMED_CHARACTER_REFERENCES[idx].1, len))
+        })
+        .filter(|(_, len)| {
+            rest[..*len].ends_with(';')
+                || !rest[*len..].starts_with(|c: char| c == '=' || c.is_ascii_alphanumeric())
+        })
+}
+
+/// The character of the numeric reference like `#233;` at the start of `rest`, which follows a `&`,
+/// and the length of the reference
+fn numeric_character_reference(rest: &str) -> Option<(char, usize)> {
+    let number = rest.strip_prefix('#')?;
+    let (digits, radix, prefix_len) = match number.strip_prefix(['x', 'X']) {
+        Some(hex) => (hex, 16, 2),
+        None => (number, 10, 1),
+    };
+    let digits_len = digits
+        .find(|c: char| !c.is_digit(radix))
+        .unwrap_or(digits.len());
+    if digits_len == 0 {
+        return None;
+    }
+    // Numbers too large for any character are replaced like the other invalid ones
+    let code = u32::from_str_radix(&digits[..digits_len], radix).unwrap_or(u32::MAX);
+    let len = prefix_len + digits_len + usize::from(digits[digits_len..].starts_with(';'));
+    Some((numeric_character(code), len))
+}
+
+/// The character a numeric reference stands for. Null, surrogates and numbers beyond Unicode
+/// become the replacement character, and the C1 controls the characters of Windows-1252.
+fn numeric_character(code: u32) -> char {
+    let code = match code {
+        0x80 => 0x20AC,
+        0x82 => 0x201A,
+        0x83 => 0x0192,
+        0x84 => 0x201E,
+        0x85 => 0x2026,
+        0x86 => 0x2020,
+        0x87 => 0x2021,
+        0x88 => 0x02C6,
+        0x89 => 0x2030,
+        0x8A => 0x0160,
+        0x8B => 0x2039,
+        0x8C => 0x0152,
+        0x8E => 0x017D,
+        0x91 => 0x2018,
+        0x92 => 0x2019,
+        0x93 => 0x201C,
+        0x94 => 0x201D,
+        0x95 => 0x2022,
+        0x96 => 0x2013,
+        0x97 => 0x2014,
+        0x98 => 0x02DC,
+        0x99 => 0x2122,
+        0x9A => 0x0161,
+        0x9B => 0x203A,
+        0x9C => 0x0153,
+        0x9E => 0x017E,
+        0x9F => 0x0178,
+        code => code,
+    };
+    char::from_u32(code)
+        .filter(|c| *c != '\0')
+        .unwrap_or(char::REPLACEMENT_CHARACTER)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntest::test_case;
+
+    fn attributes(input: &str) -> Vec<(String, String)> {
+        start_tags(input)
+            .into_iter()
+            .flat_map(|tag| tag.attributes)
+            .map(|a| (a.name, a.value))
+            .collect()
+    }
+
+    #[test_case("<a href=\"x y\">", "x y")]
+    #[test_case("<a href='x y'>", "x y")]
+    #[test_case("<a href=x>", "x")]
+    #[test_case("<a href = \"x\">", "x")]
+    #[test_case("<A HREF=\"x\">", "x")]
+    #[test_case("<a\nhref\n=\n\"x\"\n>", "x")]
+    #[test_case("<a href=\"x?a=1&amp;b=2\">", "x?a=1&b=2")]
+    #[test_case("<a href=\"&#120;&#x79;&unknown;\">", "xy&unknown;")]
+    #[test_case("<a href=\"a>b\">", "a>b")]
+    #[test_case("<a href=x/>", "x/")]
+    fn attribute_values(input: &str, value: &str) {
+        assert_eq!(
+            vec![("href".to_string(), value.to_string())],
+            attributes(input)
+        );
+    }
+
+    #[test_case("<a href=\"a&hellip;b\">", "a\u{2026}b")]
+    #[test_case("<a href=\"caf&eacute;.html\">", "caf\u{e9}.html")]
+    #[test_case("<a href=\"&NotNestedGreaterGreater;\">", "\u{2aa2}\u{338}")]
+    #[test_case("<a href=\"&copy 2024\">", "\u{a9} 2024")]
+    #[test_case("<a href=\"?a=1&copy=2\">", "?a=1&copy=2")]
+    #[test_case("<a href=\"&notit;\">", "&notit;")]
+    #[test_case("<a href=\"&notin;\">", "\u{2209}")]
+    #[test_case("<a href=\"&#65b\">", "Ab")]
+    #[test_case("<a href=\"&#x80;\">", "\u{20ac}")]
+    #[test_case(
+        "<a href=\"&#0;&#xD800;&#1114112;&#99999999999;\">",
+        "\u{fffd}\u{fffd}\u{fffd}\u{fffd}"
+    )]
+    #[test_case("<a href=\"&#;&#x;&;& \">", "&#;&#x;&;& ")]
+    fn character_references(input: &str, value: &str) {
+        assert_eq!(
+            vec![("href".to_string(), value.to_string())],
+            attributes(input)
+        );
+    }
+
+    #[test_case("<ImG SrC=x>", "img", "src", "x")]
+    #[test_case("<img src=x />", "img", "src", "x")]
+    #[test_case("<img/src=x>", "img", "src", "x")]
+    #[test_case("<img src=\"x\"alt=y>", "img", "alt", "y")]
+    #[test_case("<a =x>", "a", "=x", "")]
+    fn tag_states(input: &str, name: &str, attribute: &str, value: &str) {
+        let result = start_tags(input);
+        assert_eq!(name, result[0].name);
+        let found = result[0]
+            .attributes
+            .iter()
+            .find(|a| a.name == attribute)
+            .unwrap();
+        assert_eq!(value, found.value);
+    }
+
+    #[test_case("<!--><a href=x>")]
+    #[test_case("<!---><a href=x>")]
+    #[test_case("<!-- a --!><a href=x>")]
+    #[test_case("</p title=\"a>b\"><a href=x>")]
+    #[test_case("</ p><a href=x>")]
+    #[test_case("<script></scripts><img src=y></script><a href=x>")]
+    #[test_case("<TITLE><img src=y></title ><a href=x>")]
+    fn markup_before_tag(input: &str) {
+        assert_eq!(
+            vec![("href".to_string(), "x".to_string())],
+            attributes(input)
+                .into_iter()
+                .filter(|(name, _)| name == "href")
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn plaintext() {
+        let result = start_tags("<plaintext><a href=x>");
+        assert_eq!(1, result.len());
+    }
+
+    #[test]
+    fn attributes_without_value() {
+        let result = attributes("<input disabled type=checkbox/>");
+        assert_eq!(
+            vec![
+                ("disabled".to_string(), String::new()),
+                ("type".to_string(), "checkbox/".to_string())
+            ],
+            result
+        );
+    }
+
+    #[test]
+    fn duplicate_attribute() {
+        let result = attributes("<a href=\"first\" href=\"second\">");
+        assert_eq!(vec![("href".to_string(), "first".to_string())], result);
+    }
+
+    #[test_case("<!-- <a href=\"x\"> -->")]
+    #[test_case("<!DOCTYPE html>")]
+    #[test_case("<?xml version=\"1.0\"?>")]
+    #[test_case("</a href=\"x\">")]
+    #[test_case("1 < 2 and <3")]
+    #[test_case("<![CDATA[ <a href=\"x\"> ]]>")]
+    fn no_start_tag(input: &str) {
+        assert!(start_tags(input).is_empty());
+    }
+
+    #[test]
+    fn raw_text() {
+        let input = "<script>if (a <b) { s = '<a href=\"x\">'; }</SCRIPT><img src=\"y\">";
+        let result = start_tags(input);
+        let names: Vec<&str> = result.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(vec!["script", "img"], names);
+        assert_eq!(input.find("<img").unwrap(), result[1].start);
+    }
+
+    #[test]
+    fn positions() {
+        let input = "ü <p>\n<a  class=x href=\"y\">";
+        let result = start_tags(input);
+        assert_eq!(3, result[0].start);
+        assert_eq!(input.find("<a").unwrap(), result[1].start);
+        assert_eq!(input.find("href").unwrap(), result[1].attributes[1].start);
+        let y = input.find('y').unwrap();
+        assert_eq!(y..y + 1, result[1].attributes[1].value_range);
+    }
+
+    #[test_case("<a href=\"x y\">", "x y")]
+    #[test_case("<a href='x'>", "x")]
+    #[test_case("<a href=x>", "x")]
+    #[test_case("<a href=\"x&amp;y\">", "x&amp;y")]
+    #[test_case("<a href>", "")]
+    fn raw_values(input: &str, raw: &str) {
+        let result = start_tags(input);
+        let attribute = &result[0].attributes[0];
+        assert_eq!(raw, &input[attribute.value_range.clone()]);
+    }
+
+    #[test]
+    fn unterminated() {
+        let result = attributes("<a href=\"x");
+        assert_eq!(vec![("href".to_string(), "x".to_string())], result);
+    }
+}
@@ -0,0 +1,406 @@
+use crate::link_extractors::link_extractor::line_column_from_idx;
+use crate::link_extractors::link_extractor::trimmed_range;
+use crate::link_extractors::link_extractor::LinkExtractor;
+use crate::link_extractors::link_extractor::LinkKind;
+use crate::link_extractors::link_extractor::MarkupLink;
+use crate::link_extractors::link_extractor::Span;
+use regex::Regex;
+use std::ops::Range;
+use std::path::Path;
+
+pub struct LatexLinkExtractor();
+
+lazy_static! {
+    static ref VERBATIM_BEGIN_REGEX: Regex =
+        Regex::new(r"\\begin\{(verbatim\*?|Verbatim|lstlisting|minted|comment)\}").unwrap();
+    // Arguments of `\url` and `\href` may contain `%` characters
+    static ref URL_ARGUMENT_REGEX: Regex =
+        Regex::new(r"\\(?:url|href)\s*(?:\[[^\]]*\])?\s*\{[^}\n]*\}").unwrap();
+    static ref COMMAND_REGEX: Regex = Regex::new(
+        r"\\(href|url|includegraphics|input|include|bibliography|addbibresource)\*?\s*(?:\[[^\]]*\]\s*)?\{([^}]*)\}(?:\s*\{([^}\n]*)\})?"
+    )
+    .unwrap();
+    // The plain TeX form `\input file`
+    static ref PLAIN_INPUT_REGEX: Regex = Regex::new(r"\\input[ \t]+([^\s{}\\%]+)").unwrap();
+    // A parameter of a macro like `#1`, but not an escaped `\#1`
+    static ref MACRO_PARAMETER_REGEX: Regex = Regex::new(r"(?:^|[^\\])#+\d").unwrap();
+    static ref GRAPHICS_PATH_REGEX: Regex = Regex::new(r"\\graphicspath\s*\{((?:\s*\{[^{}]*\})*)\s*\}").unwrap();
+    static ref GRAPHICS_PATH_DIR_REGEX: Regex = Regex::new(r"\{([^{}]*)\}").unwrap();
+}
+
+/// The directories of the last `\graphicspath{{figures/}{images/}}` of a document,
+/// which graphicx searches for graphics after the directory of the main document
+#[must_use]
+pub fn graphics_paths(text: &str) -> Vec<String> {
+    let code = mask_comments_and_verbatim(text);
+    GRAPHICS_PATH_REGEX
+        .captures_iter(&code)
+        .last()
+        .map(|caps| {
+            GRAPHICS_PATH_DIR_REGEX
+                .captures_iter(&caps[1])
+                .map(|dir| dir[1].trim().to_string())
+                .filter(|dir| !dir.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// A link with its byte index, the byte range of its target, the target, kind and text
+type FoundLink = (usize, Range<usize>, String, LinkKind, Option<String>);
+
+impl LinkExtractor for LatexLinkExtractor {
+    fn find_links(&self, text: &str) -> Vec<MarkupLink> {
+        let code = mask_comments_and_verbatim(text);
+        let mut result: Vec<FoundLink> = Vec::new();
+        for caps in COMMAND_REGEX.captures_iter(&code) {
+            let start = caps.get(0).unwrap().start();
+            let range = trimmed_range(&code, caps.get(2).unwrap().range());
+            let argument = &code[range.clone()];
+            // Arguments of macros are no links, like `#1` in a `\newcommand`
+            if MACRO_PARAMETER_REGEX.is_match(argument) && !argument.contains("://") {
+                continue;
+            }
+            match &caps[1] {
+                "href" => {
+                    // The text of `\href{url}{text}`
+                    let text = caps
+                        .get(3)
+                        .map(|t| t.as_str().trim().to_string())
+                        .filter(|t| !t.is_empty());
+                    let target = unescape(argument);
+                    result.push((start, range, target, LinkKind::Inline, text));
+                }
+                "url" => {
+                    let target = argument.to_string();
+                    result.push((start, range, target, LinkKind::Autolink, None));
+                }
+                "includegraphics" => {
+                    // graphicx tries several extensions, so they are looked up when validating
+                    let target = argument.to_string();
+                    result.push((start, range, target, LinkKind::Image, None));
+                }
+                "addbibresource" => {
+                    let target = argument.to_string();
+                    result.push((start, range, target, LinkKind::Inline, None));
+                }
+                "input" | "include" => {
+                    let target = with_extension(argument, "tex");
+                    result.push((start, range, target, LinkKind::Inline, None));
+                }
+                _ => {
+                    // `\bibliography{refs,other}` uses `refs.bib` and `other.bib`
+                    let mut name_start = range.start;
+                    for name in argument.split(',') {
+                        let name_range = trimmed_range(&code, name_start..name_start + name.len());
+                        name_start += name.len() + 1;
+                        if !name_range.is_empty() {
+                            let target = with_extension(&code[name_range.clone()], "bib");
+                            result.push((start, name_range, target, LinkKind::Inline, None));
+                        }
+                    }
+                }
+            }
+        }
+        for caps in PLAIN_INPUT_REGEX.captures_iter(&code) {
+            result.push((
+                caps.get(0).unwrap().start(),
+                caps.get(1).unwrap().range(),
+                with_extension(&caps[1], "tex"),
+                LinkKind::Inline,
+                None,
+            ));
+        }
+
+        result.sort_by_key(|(idx, ..)| *idx);
+        result
+            .into_iter()
+            .filter(|(_, _, target, ..)| !target.is_empty())
+            .map(|(idx, range, target, kind, link_text)| {
+                let (line, column) = line_column_from_idx(text, idx);
+                MarkupLink {
+                    line,
+                    column,
+                    target,
+                    kind,
+                    text: link_text,
+                    span: Span::new(text, range),
+                    ..MarkupLink::default()
+                }
+            })
+            .collect()
+    }
+}
+
+/// Replaces comments and verbatim text with white-space,
+/// keeping the byte offsets of the remaining text intact.
+fn mask_comments_and_verbatim(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    // The end of the current verbatim environment, like `\end{verbatim}`
+    let mut verbatim_end: Option<String> = None;
+    for (line_idx, line) in text.split('\n').enumerate() {
+        if line_idx > 0 {
+            result.push('\n');
+        }
+        let mut rest = line;
+        while !rest.is_empty() {
+            if let Some(end) = &verbatim_end {
+                let verbatim_len = match rest.find(end.as_str()) {
+                    Some(idx) => {
+                        let len = idx + end.len();
+                        verbatim_end = None;
+                        len
+                    }
+                    None => rest.len(),
+                };
+                result.push_str(&" ".repeat(verbatim_len));
+                rest = &rest[verbatim_len..];
+            } else if let Some(caps) = VERBATIM_BEGIN_REGEX.captures(rest) {
+                let begin = caps.get(0).unwrap();
+                result.push_str(&mask_line(&rest[..begin.start()]));
+                result.push_str(&" ".repeat(begin.as_str().len()));
+                verbatim_end = Some(format!("\\end{{{}}}", &caps[1]));
+                rest = &rest[begin.end()..];
+            } else {
+                result.push_str(&mask_line(rest));
+                rest = "";
+            }
+        }
+    }
+    result
+}
+
+/// Masks the comment and `\verb` parts of a single line.
+fn mask_line(line: &str) -> String {
+    let mut line = line.to_string();
+    // `\verb|text|` may use any delimiter
+    let mut search_start = 0;
+    while let Some(idx) = line[search_start..]
+        .find("\\verb")
+        .map(|idx| idx + search_start)
+    {
+        let argument = line[idx + 5..].trim_start_matches('*');
+        let Some(delimiter) = argument.chars().next().filter(|c| !c.is_alphabetic()) else {
+            search_start = idx + 5;
+            continue;
+        };
+        let argument_start = line.len() - argument.len() + delimiter.len_utf8();
+        let end = line[argument_start..]
+            .find(delimiter)
+            .map_or(line.len(), |end| {
+                argument_start + end + delimiter.len_utf8()
+            });
+        line.replace_range(idx..end, &" ".repeat(end - idx));
+        search_start = end;
+    }
+    let url_arguments: Vec<_> = URL_ARGUMENT_REGEX
+        .find_iter(&line)
+        .map(|m| m.range())
+        .collect();
+    let comment_start = line.char_indices().find(|(idx, c)| {
+        *c == '%'
+            && !is_escaped(&line[..*idx])
+            && !url_arguments.iter().any(|range| range.contains(idx))
+    });
+    if let Some((idx, _)) = comment_start {
+        line.replace_range(idx.., &" ".repeat(line.len() - idx));
+    }
+    line
+}
+
+/// Whether the character behind `before` is escaped by an odd number of backslashes.
+/// An even number are line breaks like `\\`.
+fn is_escaped(before: &str) -> bool {
+    (before.len() - before.trim_end_matches('\\').len()) % 2 == 1
+}
+
+/// TeX appends the default extension to file names without one.
+fn with_extension(name: &str, extension: &str) -> String {
+    if Path::new(name).extension().is_some() {
+        name.to_string()
+    } else {
+        format!("{name}.{extension}")
+    }
+}
+
+/// Removes the escaping of special characters in `\href` targets, like `\#` or `\%`.
+fn unescape(target: &str) -> String {
+    lazy_static! {
+        static ref ESCAPE_REGEX: Regex = Regex::new(r"\\([#%&_~$])").unwrap();
+    }
+    ESCAPE_REGEX.replace_all(target, "$1").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::link_extractors::link_extractor::link;
+    use crate::link_extractors::link_extractor::span_texts;
+    use ntest::test_case;
+
+    /// Only keeps the target and position of links
+    fn positions(links: Vec<MarkupLink>) -> Vec<MarkupLink> {
+        links
+            .into_iter()
+            .map(|l| link(&l.target, l.line, l.column))
+            .collect()
+    }
+
+    #[test]
+    fn no_link() {
+        let le = LatexLinkExtractor();
+        let input = "\\section{Intro}\nThis is \\emph{no} link, just \\textbf{text}.";
+        let result = le.find_links(input);
+        assert!(result.is_empty());
+    }
+
+    #[test_case("\\href{https://example.net/}{Example}", "https://example.net/", 1)]
+    #[test_case("See \\url{https://example.net/}.", "https://example.net/", 5)]
+    #[test_case(
+        "\\href{https://example.net/\\#usage}{Usage}",
+        "https://example.net/#usage",
+        1
+    )]
+    #[test_case(
+        "\\url{https://example.net/a%20b#usage}",
+        "https://example.net/a%20b#usage",
+        1
+    )]
+    #[test_case("\\href{./manual.pdf}{Manual}", "./manual.pdf", 1)]
+    #[test_case(
+        "\\href{../markdown/anchor_links.md\\#next-chapter}{anchor links}",
+        "../markdown/anchor_links.md#next-chapter",
+        1
+    )]
+    #[test_case("Umlaut ü \\url{https://example.net/}", "https://example.net/", 10)]
+    fn url(input: &str, target: &str, column: usize) {
+        let le = LatexLinkExtractor();
+        let result = le.find_links(input);
+        assert_eq!(vec![link(target, 1, column)], positions(result));
+    }
+
+    #[test_case("\\includegraphics{figures/plot.png}", "figures/plot.png")]
+    #[test_case(
+        "\\includegraphics[width=0.5\\textwidth]{figures/plot}",
+        "figures/plot"
+    )]
+    #[test_case("\\includegraphics*{plot.pdf}", "plot.pdf")]
+    #[test_case("\\input{chapters/intro}", "chapters/intro.tex")]
+    #[test_case("\\input{chapters/intro.tex}", "chapters/intro.tex")]
+    #[test_case("\\input chapters/intro", "chapters/intro.tex")]
+    #[test_case("\\include{chapters/intro}", "chapters/intro.tex")]
+    #[test_case("\\bibliography{refs}", "refs.bib")]
+    #[test_case("\\addbibresource{refs.bib}", "refs.bib")]
+    fn file(input: &str, target: &str) {
+        let le = LatexLinkExtractor();
+        let result = le.find_links(input);
+        assert_eq!(vec![link(target, 1, 1)], positions(result));
+    }
+
+    #[test_case("\\href{https://example.net/}{ Example }", "inline", "Example")]
+    #[test_case("\\href{https://example.net/}{}", "inline", "")]
+    #[test_case("\\url{https://example.net/}", "autolink", "")]
+    #[test_case("\\includegraphics[width=5cm]{plot.png}", "image", "")]
+    #[test_case("\\input{chapters/intro}", "inline", "")]
+    fn kind_and_text(input: &str, kind: &str, text: &str) {
+        let le = LatexLinkExtractor();
+        let result = le.find_links(input);
+        assert_eq!(kind.parse::<LinkKind>().unwrap(), result[0].kind);
+        assert_eq!(
+            Some(text.to_string()).filter(|t| !t.is_empty()),
+            result[0].text
+        );
+    }
+
+    #[test]
+    fn multiple_bibliographies() {
+        let le = LatexLinkExtractor();
+        let input = "\\bibliography{refs, other}";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![
+                link("refs.bib", 1, 1).spanned_as(input, "refs"),
+                link("other.bib", 1, 1).spanned_as(input, "other")
+            ],
+            result
+        );
+    }
+
+    #[test_case("% \\input{commented}")]
+    #[test_case("Text % \\url{https://example.net/}")]
+    #[test_case("\\verb|\\input{verbatim}|")]
+    #[test_case("\\begin{verbatim}\n\\input{verbatim}\n\\end{verbatim}")]
+    #[test_case("\\begin{lstlisting}\\url{https://example.net/}\\end{lstlisting}")]
+    #[test_case("\\newcommand{\\fig}[1]{\\includegraphics{#1}}")]
+    #[test_case("\\newcommand{\\doc}[1]{\\href{docs/#1.md}{#1}}")]
+    #[test_case("\\def\\fig#1{\\includegraphics{figures/##1}}")]
+    fn ignored(input: &str) {
+        let le = LatexLinkExtractor();
+        let result = le.find_links(input);
+        assert!(result.is_empty());
+    }
+
+    #[test_case("\\graphicspath{{figures/}{ ../images/ }}", "figures/,../images/")]
+    #[test_case("\\graphicspath{{a/}}\n\\graphicspath{{b/}}", "b/")]
+    #[test_case("% \\graphicspath{{a/}}", "")]
+    #[test_case("\\graphicspath{}", "")]
+    fn graphics_path(input: &str, dirs: &str) {
+        assert_eq!(dirs, graphics_paths(input).join(","));
+    }
+
+    #[test]
+    fn escaped_percent_is_no_comment() {
+        let le = LatexLinkExtractor();
+        let input = "100\\% \\input{chapter}";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![link("chapter.tex", 1, 7).spanned_as(input, "chapter")],
+            result
+        );
+    }
+
+    #[test_case("Line \\\\% \\input{commented}", "")]
+    #[test_case("Line \\\\\\% \\input{chapter}", "chapter.tex")]
+    fn backslashes_before_percent(input: &str, targets: &str) {
+        let le = LatexLinkExtractor();
+        let result = le.find_links(input);
+        let found: Vec<&str> = result.iter().map(|l| l.target.as_str()).collect();
+        assert_eq!(targets, found.join(","));
+    }
+
+    #[test]
+    fn after_verbatim() {
+        let le = LatexLinkExtractor();
+        let input = "\\begin{verbatim}\n\\input{a}\n\\end{verbatim} \\input{b}\n\\input{c}";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![
+                link("b.tex", 3, 16).spanned_as(input, "b"),
+                link("c.tex", 4, 1).spanned_as(input, "c")
+            ],
+            result
+        );
+    }
+
+    #[test_case(
+        "\\href{ https://example.net/\\#a }{Example}",
+        "https://example.net/\\#a"
+    )]
+    #[test_case("\\url{https://example.net/}", "https://example.net/")]
+    #[test_case("\\includegraphics[width=2cm]{logo}", "logo")]
+    #[test_case("\\input{chapter}", "chapter")]
+    #[test_case("\\input chapter", "chapter")]
+    fn span(input: &str, raw: &str) {
+        let le = LatexLinkExtractor();
+        let result = le.find_links(input);
+        assert_eq!(vec![raw], span_texts(input, &result));
+    }
+
+    #[test]
+    fn bibliography_spans() {
+        let le = LatexLinkExtractor();
+        let input = "\\bibliography{refs, other}";
+        let result = le.find_links(input);
+        assert_eq!(vec!["refs", "other"], span_texts(input, &result));
+    }
+}
@@ -1,12 +1,26 @@
+use super::asciidoc_link_extractor::AsciiDocLinkExtractor;
+use super::directives::Suppressions;
 use super::html_link_extractor::HtmlLinkExtractor;
+use super::latex_link_extractor::LatexLinkExtractor;
 use super::markdown_link_extractor::MarkdownLinkExtractor;
+use super::mdx_link_extractor::MdxLinkExtractor;
+use super::notebook_link_extractor::NotebookLinkExtractor;
+use super::org_link_extractor::OrgLinkExtractor;
+use super::plain_link_extractor::PlainLinkExtractor;
+use super::rst_link_extractor::RstLinkExtractor;
+use super::source_code_link_extractor::{CommentSyntax, SourceCodeLinkExtractor};
+use super::svg_link_extractor::SvgLinkExtractor;
 use crate::markup::{MarkupFile, MarkupType};
+use crate::Config;
+use serde::Deserialize;
 use std::env;
 use std::fmt;
 use std::fs;
+use std::ops::Range;
+use std::str::FromStr;
 
 /// Link found in markup files
-#[derive(Eq, PartialEq, Clone)]
+#[derive(Eq, PartialEq, Clone, Default)]
 pub struct MarkupLink {
     /// The source file of the link
     pub source: String,
@@ -14,50 +28,202 @@ pub struct MarkupLink {
     pub target: String,
     /// The line number were the link was found
     pub line: usize,
-    /// The column number were the link was found
+    /// The column number were the link was found, counted in characters
     pub column: usize,
+    /// The source range of the target as written, like `url` in `[text](url)`.
+    /// Reference links span the destination of their definition.
+    pub span: Span,
+    /// The notebook cell, starting at 1, the link was found in.
+    /// Line, column and span are relative to this cell.
+    pub cell: Option<usize>,
+    /// The base relative links are resolved against instead of the source file,
+    /// like the `href` of an HTML `<base>` element
+    pub base: Option<String>,
+    /// A problem found during extraction, which is reported instead of checking the target
+    pub issue: Option<LinkIssue>,
+    /// The syntax the link is written in
+    pub kind: LinkKind,
+    /// The text of the link, or the alt text of an image
+    pub text: Option<String>,
+    /// The title of the link, like `title` in `[text](url "title")`
+    pub title: Option<String>,
+    /// The name of the HTML attribute containing the target, like `href`
+    pub attribute: Option<String>,
+}
+
+/// A location in a document. Lines and columns start at 1.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    /// The byte index into the document
+    pub offset: usize,
+    pub line: usize,
+    /// The column counted in UTF-8 bytes
+    pub byte_column: usize,
+    /// The column counted in Unicode scalar values
+    pub char_column: usize,
+    /// The column counted in UTF-16 code units, as used by the Language Server Protocol
+    pub utf16_column: usize,
+}
+
+impl Position {
+    /// The position of the byte index `offset` into `text`
+    #[must_use]
+    pub fn new(text: &str, offset: usize) -> Self {
+        let before = &text[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line_text = &before[line_start..];
+        Self {
+            offset,
+            line: before.matches('\n').count() + 1,
+            byte_column: line_text.len() + 1,
+            char_column: line_text.chars().count() + 1,
+            utf16_column: line_text.encode_utf16().count() + 1,
+        }
+    }
+}
+
+/// A range of a document from the start up to, but excluding, the end
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Span {
+    /// The span of the byte range `range` of `text`
+    #[must_use]
+    pub fn new(text: &str, range: Range<usize>) -> Self {
+        Self {
+            start: Position::new(text, range.start),
+            end: Position::new(text, range.end),
+        }
+    }
+
+    /// The byte range of the span
+    #[must_use]
+    pub const fn range(&self) -> Range<usize> {
+        self.start.offset..self.end.offset
+    }
+}
+
+/// The syntax of a link, which links can be selected by with the `link-kinds` option
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum LinkKind {
+    /// A link like `[text](url)`, or a link of markup without further distinction
+    #[default]
+    Inline,
+    /// A reference link like `[text][label]`, or a reference definition like `[label]: url`
+    Reference,
+    /// An autolink like `<https://example.net>`, or a bare URL
+    Autolink,
+    /// An image like `![alt](image.png)` or `<img src="image.png">`
+    Image,
+    /// An attribute of an HTML element like `<a href="url">`
+    Html,
+}
+
+impl FromStr for LinkKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "inline" => Ok(Self::Inline),
+            "reference" => Ok(Self::Reference),
+            "autolink" => Ok(Self::Autolink),
+            "image" => Ok(Self::Image),
+            "html" => Ok(Self::Html),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for LinkKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::Inline => "inline",
+            Self::Reference => "reference",
+            Self::Autolink => "autolink",
+            Self::Image => "image",
+            Self::Html => "html",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Problems of links which are found without checking their target
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub enum LinkIssue {
+    /// A reference-style link like `[text][label]` without a definition for the label
+    UndefinedReference(String),
+    /// A reference definition like `[label]: url` which is never used
+    UnusedDefinition(String),
+    /// A reference to a label like `` :ref:`label` `` which is not defined in the document
+    UndefinedLabel(String),
+    /// A link like `attachment:image.png` to an attachment missing in its notebook cell
+    MissingAttachment(String),
+    /// A link within the range of an inline directive like `<!-- mlc-disable-next-line -->`
+    Suppressed(String),
 }
 
 impl fmt::Debug for MarkupLink {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{} => {} (line {}, column {})",
-            self.source, self.target, self.line, self.column
+            "{} => {} ({}line {}, column {})",
+            self.source,
+            self.target,
+            self.cell
+                .map(|cell| format!("cell {cell}, "))
+                .unwrap_or_default(),
+            self.line,
+            self.column
         )
     }
 }
 
 impl MarkupLink {
+    /// The line and column of the link, prefixed by the notebook cell if there is one
+    #[must_use]
+    pub fn position_str(&self) -> String {
+        match self.cell {
+            Some(cell) => format!("cell {}, {}, {}", cell, self.line, self.column),
+            None => format!("{}, {}", self.line, self.column),
+        }
+    }
+
     #[must_use]
     pub fn source_str(&self) -> String {
         lazy_static! {
             static ref IS_VS_CODE_TERMINAL: bool =
                 env::var("TERM_PROGRAM") == Ok("vscode".to_string());
         }
-        if *IS_VS_CODE_TERMINAL {
+        // Editors can not jump to the lines of notebook cells
+        if *IS_VS_CODE_TERMINAL && self.cell.is_none() {
             format!(
                 "{}:{}:{} => {}",
                 self.source, self.line, self.column, self.target
             )
         } else {
             format!(
-                "{} ({}, {}) => {}",
-                self.source, self.line, self.column, self.target
+                "{} ({}) => {}",
+                self.source,
+                self.position_str(),
+                self.target
             )
         }
     }
 }
 
 #[must_use]
-pub fn find_links(file: &MarkupFile) -> Vec<MarkupLink> {
+pub fn find_links(file: &MarkupFile, config: &Config) -> Vec<MarkupLink> {
     let path = &file.path;
-    let link_extractor = link_extractor_factory(file.markup_type);
+    let link_extractor = link_extractor_factory(file, config);
 
     info!("Scannig file at path '{}' for links ...", path);
     match fs::read_to_string(path) {
         Ok(text) => {
             let mut links = link_extractor.find_links(&text);
+            Suppressions::find(&text, file.markup_type).apply(&mut links);
             for l in &mut links {
                 l.source = path.to_string();
             }
@@ -73,13 +239,155 @@ pub fn find_links(file: &MarkupFile) -> Vec<MarkupLink> {
     }
 }
 
-fn link_extractor_factory(markup_type: MarkupType) -> Box<dyn LinkExtractor> {
-    match markup_type {
-        MarkupType::Markdown => Box::new(MarkdownLinkExtractor()),
+fn link_extractor_factory(file: &MarkupFile, config: &Config) -> Box<dyn LinkExtractor> {
+    match file.markup_type {
+        MarkupType::Markdown => Box::new(markdown_link_extractor(config)),
+        MarkupType::Mdx => Box::new(MdxLinkExtractor(markdown_link_extractor(config))),
         MarkupType::Html => Box::new(HtmlLinkExtractor()),
+        MarkupType::Rst => Box::new(RstLinkExtractor()),
+        MarkupType::AsciiDoc => Box::new(AsciiDocLinkExtractor()),
+        MarkupType::Org => Box::new(OrgLinkExtractor()),
+        MarkupType::Latex => Box::new(LatexLinkExtractor()),
+        MarkupType::Notebook => Box::new(NotebookLinkExtractor {
+            markdown: markdown_link_extractor(config),
+            html_outputs: config.optional.notebook_outputs.unwrap_or_default(),
+        }),
+        MarkupType::Svg => Box::new(SvgLinkExtractor()),
+        MarkupType::SourceCode => Box::new(SourceCodeLinkExtractor {
+            syntax: CommentSyntax::for_file(&file.path, config),
+        }),
+        MarkupType::Plain => Box::new(PlainLinkExtractor::for_file(&file.path, config)),
+    }
+}
+
+fn markdown_link_extractor(config: &Config) -> MarkdownLinkExtractor {
+    MarkdownLinkExtractor {
+        dialect: config.optional.markdown_dialect.unwrap_or_default(),
+        front_matter_keys: config
+            .optional
+            .front_matter_keys
+            .clone()
+            .unwrap_or_default(),
+        wiki_links: config.optional.wiki_links.unwrap_or_default(),
+    }
+}
+
+/// Converts a byte index into `text` to a line and a column, both starting at 1.
+/// The column is counted in characters.
+pub(crate) fn line_column_from_idx(text: &str, idx: usize) -> (usize, usize) {
+    let position = Position::new(text, idx);
+    (position.line, position.char_column)
+}
+
+/// The byte range within `range` of `text` without surrounding white-space
+pub(crate) fn trimmed_range(text: &str, range: Range<usize>) -> Range<usize> {
+    let value = &text[range.clone()];
+    let start = range.start + value.len() - value.trim_start().len();
+    start..(range.start + value.trim_end().len()).max(start)
+}
+
+/// Trailing punctuation and unbalanced closing parentheses are not part of bare URLs.
+pub(crate) fn trim_url(url: &str) -> &str {
+    let url = url.trim_end_matches(['.', ',', ';', ':', '!', '?']);
+    if url.ends_with(')') && url.matches(')').count() > url.matches('(').count() {
+        &url[..url.len() - 1]
+    } else {
+        url
     }
 }
 
 pub trait LinkExtractor {
     fn find_links(&self, text: &str) -> Vec<MarkupLink>;
 }
+
+#[cfg(test)]
+impl MarkupLink {
+    /// The link with the span of its target, as written at or after its line and column in `text`
+    pub(crate) fn spanned(self, text: &str) -> Self {
+        let raw = self.target.clone();
+        self.spanned_as(text, &raw)
+    }
+
+    /// The link with the span of `raw`, the first occurrence at or after its line and column in `text`.
+    /// A bracketed link text written there is skipped, as the target may repeat it.
+    pub(crate) fn spanned_as(self, text: &str, raw: &str) -> Self {
+        let line_start = text
+            .split_inclusive('\n')
+            .take(self.line - 1)
+            .map(str::len)
+            .sum::<usize>();
+        let position = text[line_start..]
+            .char_indices()
+            .nth(self.column - 1)
+            .map_or(text.len(), |(idx, _)| line_start + idx);
+        let position = match &self.text {
+            Some(link_text) if text[position..].starts_with(&format!("[{link_text}]")) => {
+                position + link_text.len() + 2
+            }
+            _ => position,
+        };
+        let start = position
+            + text[position..]
+                .find(raw)
+                .unwrap_or_else(|| panic!("'{}' is not written behind the link", raw));
+        Self {
+            span: Span::new(text, start..start + raw.len()),
+            ..self
+        }
+    }
+}
+
+/// A link to `target` at `line` and `column`, with all other fields at their defaults
+#[cfg(test)]
+pub(crate) fn link(target: &str, line: usize, column: usize) -> MarkupLink {
+    MarkupLink {
+        target: target.to_string(),
+        line,
+        column,
+        ..MarkupLink::default()
+    }
+}
+
+/// The text within the span of each link, to check spans in tests
+#[cfg(test)]
+pub(crate) fn span_texts<'a>(text: &'a str, links: &[MarkupLink]) -> Vec<&'a str> {
+    links.iter().map(|link| &text[link.span.range()]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntest::test_case;
+
+    #[test_case("abc", 2, 1, 3, 3, 3)]
+    #[test_case("a\nbc", 3, 2, 2, 2, 2)]
+    #[test_case("ü x", 3, 1, 4, 3, 3)]
+    #[test_case("😀 x", 5, 1, 6, 3, 4)]
+    #[test_case("x\r\n😀ü x", 9, 2, 7, 3, 4)]
+    fn position(text: &str, offset: usize, line: usize, byte: usize, char: usize, utf16: usize) {
+        let position = Position::new(text, offset);
+        assert_eq!(offset, position.offset);
+        assert_eq!(line, position.line);
+        assert_eq!(byte, position.byte_column);
+        assert_eq!(char, position.char_column);
+        assert_eq!(utf16, position.utf16_column);
+    }
+
+    #[test]
+    fn span() {
+        let text = "ä\n[😀](b.md)";
+        let start = text.find("b.md").unwrap();
+        let span = Span::new(text, start..start + 4);
+        assert_eq!(start..start + 4, span.range());
+        assert_eq!(
+            (2, 8, 5, 6),
+            (
+                span.start.line,
+                span.start.byte_column,
+                span.start.char_column,
+                span.start.utf16_column
+            )
+        );
+        assert_eq!(12, span.end.byte_column);
+    }
+}
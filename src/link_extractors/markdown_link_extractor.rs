@@ -1,102 +1,649 @@
+use super::front_matter::FrontMatter;
 use super::html_link_extractor::HtmlLinkExtractor;
+use crate::link_extractors::link_extractor::line_column_from_idx;
 use crate::link_extractors::link_extractor::LinkExtractor;
+use crate::link_extractors::link_extractor::LinkIssue;
+use crate::link_extractors::link_extractor::LinkKind;
 use crate::link_extractors::link_extractor::MarkupLink;
-use pulldown_cmark::{BrokenLink, Event, Options, Parser, Tag};
+use crate::link_extractors::link_extractor::Span;
+use crate::markup::MarkdownDialect;
+use pulldown_cmark::{BrokenLink, Event, LinkType, Options, Parser, RefDefs, Tag};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
 
-pub struct MarkdownLinkExtractor();
+#[derive(Default)]
+pub struct MarkdownLinkExtractor {
+    pub dialect: MarkdownDialect,
+    /// Keys of the front matter, whose values are checked as links
+    pub front_matter_keys: Vec<String>,
+    /// Extract wiki links like `[[Page Name]]`
+    pub wiki_links: bool,
+}
 
 impl LinkExtractor for MarkdownLinkExtractor {
     fn find_links(&self, text: &str) -> Vec<MarkupLink> {
-        let html_extractor = HtmlLinkExtractor();
+        let front_matter = FrontMatter::find(text);
+        let front_matter_links = front_matter
+            .as_ref()
+            .map(|f| f.values(text, &self.front_matter_keys))
+            .unwrap_or_default();
+        let masked_text = front_matter.map(|f| f.mask(text));
+        let text = masked_text.as_deref().unwrap_or(text);
 
-        // Setup callback that sets the URL and title when it encounters
-        // a reference to our home page.
+        // Brackets without a label, like `[x]` in task lists, are no references
+        let mut undefined_references: Vec<Reference> = Vec::new();
         let callback = &mut |broken_link: BrokenLink| {
-            warn!("Broken reference link: {:?}", broken_link.reference);
-            //TODO: Return error state
+            if broken_link.link_type != LinkType::Shortcut {
+                undefined_references.push(undefined_reference(text, &broken_link));
+            }
             None
         };
 
-        let parser = Parser::new_with_broken_link_callback(text, Options::empty(), Some(callback));
+        let options = parser_options(self.dialect);
+        let parser =
+            Parser::new_with_broken_link_callback(text, options, Some(callback)).into_offset_iter();
+        let mut definitions = definitions(text, parser.reference_definitions());
+        let destinations = destinations(&definitions);
+        // Footnotes like `[^1]` are labeled with a leading `^`
+        let mut footnote_references: Vec<Reference> = Vec::new();
+        let mut used_labels: HashSet<String> = HashSet::new();
 
-        let mut line_lengths: Vec<usize> = Vec::new();
-        let mut current_line_len = 0;
-        for c in text.chars() {
-            current_line_len += c.len_utf8();
-            if c == '\n' {
-                line_lengths.push(current_line_len);
-                current_line_len = 0;
-            }
-        }
-
-        let line_column_from_idx = |idx: usize| -> (usize, usize) {
-            let mut line = 1;
-            let mut column = idx + 1;
-            for line_length in &line_lengths {
-                if *line_length >= column {
-                    return (line, column);
-                }
-                column -= line_length;
-                line += 1;
-            }
-            (line, column)
+        let line_lengths = line_lengths(text);
+        let link_at = |idx: usize, target_range: Range<usize>, target: String, kind: LinkKind| {
+            let (line, column) = line_column(text, &line_lengths, idx);
+            new_link(line, column, Span::new(text, target_range), target, kind)
         };
 
         let mut result: Vec<MarkupLink> = Vec::new();
-        for (evt, range) in parser.into_offset_iter() {
+        // Source ranges of text, which may contain bare URLs
+        let mut text_ranges: Vec<Range<usize>> = Vec::new();
+        // The texts of the links and images the parser is within
+        let mut link_texts: Vec<String> = Vec::new();
+        let mut in_code_block = false;
+        let autolinks_enabled = self.dialect == MarkdownDialect::GitHub;
+        for (evt, range) in parser {
             match evt {
+                Event::Start(Tag::Link(..) | Tag::Image(..)) => link_texts.push(String::new()),
+                Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+                Event::Text(_)
+                    if (autolinks_enabled || self.wiki_links)
+                        && link_texts.is_empty()
+                        && !in_code_block =>
+                {
+                    text_ranges.push(range);
+                }
+                Event::Text(text) | Event::Code(text) => push_text(&mut link_texts, &text),
+                Event::SoftBreak | Event::HardBreak => push_text(&mut link_texts, " "),
+                Event::FootnoteReference(label) => {
+                    let label = format!("^{label}");
+                    used_labels.insert(normalize_label(&label));
+                    let label_range = range.start + 1..range.end - 1;
+                    footnote_references.push((range.start, label_range, label));
+                }
+                Event::Start(Tag::FootnoteDefinition(label)) => {
+                    definitions.push(Definition::footnote(&label, range.start));
+                }
                 Event::End(tag) => {
+                    let is_image = matches!(tag, Tag::Image(..));
                     match tag {
-                        Tag::Link(_link_type, destination, _title)
-                        | Tag::Image(_link_type, destination, _title) => {
-                            let line_col = line_column_from_idx(range.start);
-                            result.push(MarkupLink {
-                                line: line_col.0,
-                                column: line_col.1,
-                                source: String::new(),
-                                target: destination.to_string(),
-                            });
+                        Tag::CodeBlock(_) => in_code_block = false,
+                        Tag::Link(link_type, destination, title)
+                        | Tag::Image(link_type, destination, title) => {
+                            let source = &text[range.clone()];
+                            if let Some(label) = reference_label(link_type, source) {
+                                used_labels.insert(normalize_label(&source[label]));
+                            }
+                            let kind = link_kind(link_type, is_image);
+                            let target_range =
+                                destination_range(link_type, source, range.start, &destinations);
+                            let mut link =
+                                link_at(range.start, target_range, destination.to_string(), kind);
+                            link.text = link_texts.pop().filter(|t| !t.is_empty());
+                            link.title = Some(title.to_string()).filter(|t| !t.is_empty());
+                            result.push(link);
                         }
                         _ => (),
                     };
                 }
-                Event::Html(html) => {
-                    let line_col = line_column_from_idx(range.start);
-                    let mut html_result = html_extractor.find_links(html.as_ref());
-                    html_result = html_result
-                        .iter()
-                        .map(|md_link| {
-                            let line = line_col.0 + md_link.line - 1;
-                            let column = if md_link.line > 1 {
-                                md_link.column
-                            } else {
-                                line_col.1 + md_link.column - 1
-                            };
-                            MarkupLink {
-                                column,
-                                line,
-                                source: md_link.source.clone(),
-                                target: md_link.target.clone(),
-                            }
-                        })
-                        .collect();
-                    result.append(&mut html_result);
-                }
+                Event::Html(html) => result.append(&mut html_links(text, &html, range.start)),
                 _ => (),
             };
         }
+
+        for (idx, target) in front_matter_links {
+            let target_range = idx..idx + target.len();
+            result.push(link_at(idx, target_range, target, LinkKind::Inline));
+        }
+        for (range, target, kind, link_text) in self.text_links(text, text_ranges) {
+            let mut link = link_at(range.start, range, target, kind);
+            link.text = link_text;
+            result.push(link);
+        }
+        let issues = reference_issues(
+            undefined_references,
+            footnote_references,
+            definitions,
+            &used_labels,
+        );
+        for (idx, target_range, target, issue) in issues {
+            let mut link = link_at(idx, target_range, target, LinkKind::Reference);
+            link.issue = Some(issue);
+            result.push(link);
+        }
+        result
+    }
+}
+
+impl MarkdownLinkExtractor {
+    /// The bare URLs and wiki links of the text within `ranges` with their byte range,
+    /// kind and the alias of wiki links
+    fn text_links(
+        &self,
+        text: &str,
+        ranges: Vec<Range<usize>>,
+    ) -> Vec<(Range<usize>, String, LinkKind, Option<String>)> {
+        let mut result = Vec::new();
+        for range in merge_adjacent(ranges) {
+            let range_text = &text[range.clone()];
+            let mut links = Vec::new();
+            if self.dialect == MarkdownDialect::GitHub {
+                let urls = autolinks(range_text);
+                links.extend(
+                    urls.into_iter()
+                        .map(|(range, url)| (range, url, LinkKind::Autolink, None)),
+                );
+            }
+            if self.wiki_links {
+                links.append(&mut wiki_links(range_text));
+                links.sort_by_key(|(range, ..)| range.start);
+            }
+            result.extend(links.into_iter().map(|(r, url, kind, alias)| {
+                (range.start + r.start..range.start + r.end, url, kind, alias)
+            }));
+        }
         result
     }
 }
 
+/// The links of inline html or an html block starting at byte index `start` of `text`
+fn html_links(text: &str, html: &str, start: usize) -> Vec<MarkupLink> {
+    let line_col = line_column_from_idx(text, start);
+    HtmlLinkExtractor()
+        .find_links(html)
+        .iter()
+        .map(|md_link| {
+            let line = line_col.0 + md_link.line - 1;
+            let column = if md_link.line > 1 {
+                md_link.column
+            } else {
+                line_col.1 + md_link.column - 1
+            };
+            let span = md_link.span.range();
+            let span_start = html_source_offset(text, html, start, span.start);
+            MarkupLink {
+                column,
+                line,
+                span: Span::new(text, span_start..span_start + span.len()),
+                base: None,
+                ..md_link.clone()
+            }
+        })
+        .collect()
+}
+
+/// The byte index into `text` of the byte index `idx` of `html`, which starts at `start`.
+/// Lines after the first may lack the indentation of containers like list items.
+fn html_source_offset(text: &str, html: &str, start: usize, idx: usize) -> usize {
+    let lines_before = html[..idx].matches('\n').count();
+    if lines_before == 0 {
+        return start + idx;
+    }
+    let html_line_start = html[..idx].rfind('\n').map_or(0, |i| i + 1);
+    let html_line_end = html[idx..].find('\n').map_or(html.len(), |i| idx + i);
+    let text_line_start = text[start..]
+        .match_indices('\n')
+        .nth(lines_before - 1)
+        .map_or(text.len(), |(i, _)| start + i + 1);
+    let text_line = &text[text_line_start..];
+    text_line
+        .find(&html[html_line_start..html_line_end])
+        .map_or(text_line_start, |i| {
+            text_line_start + i + idx - html_line_start
+        })
+}
+
+/// The byte range of the destination of a link or image, whose source starts at `start`.
+/// Reference links point to the destination of their definition.
+fn destination_range(
+    link_type: LinkType,
+    source: &str,
+    start: usize,
+    destinations: &HashMap<String, Range<usize>>,
+) -> Range<usize> {
+    let whole = start..start + source.len();
+    match link_type {
+        LinkType::Inline => {
+            inline_destination(source).map_or(whole, |range| start + range.start..start + range.end)
+        }
+        // Autolinks like `<https://example.net>`
+        LinkType::Autolink | LinkType::Email if source.len() > 1 => {
+            start + 1..start + source.len() - 1
+        }
+        LinkType::Autolink | LinkType::Email => whole,
+        LinkType::Reference
+        | LinkType::ReferenceUnknown
+        | LinkType::Collapsed
+        | LinkType::CollapsedUnknown
+        | LinkType::Shortcut
+        | LinkType::ShortcutUnknown => reference_label(link_type, source)
+            .and_then(|label| destinations.get(&normalize_label(&source[label])))
+            .cloned()
+            .unwrap_or(whole),
+    }
+}
+
+/// The byte range of the destination of an inline link like `[text](url "title")`
+fn inline_destination(source: &str) -> Option<Range<usize>> {
+    let text_end = closing_bracket(source)?;
+    let rest = source[text_end + 1..].strip_prefix('(')?;
+    Some(destination(source, source.len() - rest.trim_start().len()))
+}
+
+/// The byte range of the destination of a reference definition like `[label]: url "title"`
+fn definition_destination(source: &str) -> Option<Range<usize>> {
+    let label_end = closing_bracket(source)?;
+    let rest = source[label_end + 1..].strip_prefix(':')?;
+    Some(destination(source, source.len() - rest.trim_start().len()))
+}
+
+/// The byte range of a link destination like `url` or `<url>` starting at byte index `start`
+fn destination(source: &str, start: usize) -> Range<usize> {
+    let bytes = source.as_bytes();
+    if bytes.get(start) == Some(&b'<') {
+        let end = source[start..]
+            .find('>')
+            .map_or(source.len(), |end| start + end);
+        return start + 1..end;
+    }
+    let mut depth = 0;
+    let mut idx = start;
+    while let Some(byte) = bytes.get(idx) {
+        match byte {
+            b'\\' => idx += 1,
+            b'(' => depth += 1,
+            b')' if depth == 0 => break,
+            b')' => depth -= 1,
+            byte if byte.is_ascii_whitespace() => break,
+            _ => (),
+        }
+        idx += 1;
+    }
+    start..idx.min(source.len())
+}
+
+/// The byte index of the `]` closing the first `[` of `source`.
+/// Escaped brackets and brackets within code spans are skipped.
+fn closing_bracket(source: &str) -> Option<usize> {
+    let bytes = source.as_bytes();
+    let mut depth = 0;
+    let mut idx = 0;
+    while let Some(byte) = bytes.get(idx) {
+        match byte {
+            b'\\' => idx += 1,
+            b'`' => {
+                let run = source[idx..].len() - source[idx..].trim_start_matches('`').len();
+                let fence = &source[idx..idx + run];
+                if let Some(end) = source[idx + run..].find(fence) {
+                    idx += run + end + run - 1;
+                }
+            }
+            b'[' => depth += 1,
+            b']' if depth == 1 => return Some(idx),
+            b']' => depth -= 1,
+            _ => (),
+        }
+        idx += 1;
+    }
+    None
+}
+
+/// The kind of a link or image of the given markdown link type
+const fn link_kind(link_type: LinkType, is_image: bool) -> LinkKind {
+    match link_type {
+        _ if is_image => LinkKind::Image,
+        LinkType::Inline => LinkKind::Inline,
+        LinkType::Autolink | LinkType::Email => LinkKind::Autolink,
+        LinkType::Reference
+        | LinkType::ReferenceUnknown
+        | LinkType::Collapsed
+        | LinkType::CollapsedUnknown
+        | LinkType::Shortcut
+        | LinkType::ShortcutUnknown => LinkKind::Reference,
+    }
+}
+
+/// The syntax extensions of the markdown dialect
+const fn parser_options(dialect: MarkdownDialect) -> Options {
+    match dialect {
+        MarkdownDialect::GitHub => Options::ENABLE_TABLES
+            .union(Options::ENABLE_FOOTNOTES)
+            .union(Options::ENABLE_TASKLISTS)
+            .union(Options::ENABLE_STRIKETHROUGH),
+        MarkdownDialect::CommonMark => Options::empty(),
+    }
+}
+
+fn new_link(line: usize, column: usize, span: Span, target: String, kind: LinkKind) -> MarkupLink {
+    MarkupLink {
+        target,
+        line,
+        column,
+        span,
+        kind,
+        ..MarkupLink::default()
+    }
+}
+
+/// Appends text to the texts of all links and images the parser is within
+fn push_text(link_texts: &mut [String], text: &str) {
+    for link_text in link_texts {
+        link_text.push_str(text);
+    }
+}
+
+/// The byte length of all lines, which end with a line break
+fn line_lengths(text: &str) -> Vec<usize> {
+    let mut line_lengths: Vec<usize> = Vec::new();
+    let mut current_line_len = 0;
+    for c in text.chars() {
+        current_line_len += c.len_utf8();
+        if c == '\n' {
+            line_lengths.push(current_line_len);
+            current_line_len = 0;
+        }
+    }
+    line_lengths
+}
+
+/// Converts a byte index into `text` to a line and a column counted in characters,
+/// both starting at 1
+fn line_column(text: &str, line_lengths: &[usize], idx: usize) -> (usize, usize) {
+    let mut line = 1;
+    let mut column = idx + 1;
+    for line_length in line_lengths {
+        if *line_length >= column {
+            break;
+        }
+        column -= line_length;
+        line += 1;
+    }
+    (line, text[idx + 1 - column..idx].chars().count() + 1)
+}
+
+/// Joins ranges, which end where the next one starts. Text like `a_b` is split into several events.
+fn merge_adjacent(ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    let mut result: Vec<Range<usize>> = Vec::new();
+    for range in ranges {
+        match result.last_mut() {
+            Some(last) if last.end == range.start => last.end = range.end,
+            _ => result.push(range),
+        }
+    }
+    result
+}
+
+/// Bare URLs like `https://example.net` or `www.example.net`, which are links
+/// following the autolink extension of GitHub flavored markdown, with their byte range
+fn autolinks(text: &str) -> Vec<(Range<usize>, String)> {
+    lazy_static! {
+        static ref AUTOLINK_START_REGEX: Regex = Regex::new(r"https?://|www\.").unwrap();
+    }
+    let mut result = Vec::new();
+    let mut search_start = 0;
+    for start in AUTOLINK_START_REGEX.find_iter(text) {
+        let is_www = start.as_str() == "www.";
+        let preceding = text[..start.start()].chars().next_back();
+        let valid_preceding = match preceding {
+            None => true,
+            Some(c) if is_www => c.is_whitespace() || ['*', '_', '~', '('].contains(&c),
+            Some(c) => !c.is_alphanumeric(),
+        };
+        if start.start() < search_start || !valid_preceding {
+            continue;
+        }
+        let prefix_len = if is_www { 0 } else { start.as_str().len() };
+        let rest = &text[start.start() + prefix_len..];
+        if !is_valid_domain(rest, !is_www) {
+            continue;
+        }
+        let len = rest
+            .find(|c: char| c.is_whitespace() || c == '<')
+            .unwrap_or(rest.len());
+        let url = trim_autolink(&rest[..len]);
+        search_start = start.start() + prefix_len + url.len();
+        let range = start.start()..search_start;
+        if is_www {
+            result.push((range, format!("http://{url}")));
+        } else {
+            result.push((range, format!("{}{url}", start.as_str())));
+        }
+    }
+    result
+}
+
+/// Wiki links like `[[Page#Heading|alias]]` or `![[image.png]]` with their byte range,
+/// kind and alias. The alias is dropped from the target, which keeps the brackets
+/// to be resolved within the vault.
+fn wiki_links(text: &str) -> Vec<(Range<usize>, String, LinkKind, Option<String>)> {
+    lazy_static! {
+        static ref WIKI_LINK_REGEX: Regex =
+            Regex::new(r"(!?)\[\[([^\[\]|\n]+)(?:\|([^\[\]\n]*))?\]\]").unwrap();
+    }
+    WIKI_LINK_REGEX
+        .captures_iter(text)
+        .map(|c| {
+            let target = format!("{}[[{}]]", &c[1], c[2].trim());
+            let kind = if c[1].is_empty() {
+                LinkKind::Inline
+            } else {
+                LinkKind::Image
+            };
+            let alias = c
+                .get(3)
+                .map(|a| a.as_str().trim().to_string())
+                .filter(|a| !a.is_empty());
+            (c.get(0).map_or(0..0, |m| m.range()), target, kind, alias)
+        })
+        .collect()
+}
+
+/// Domains consist of alphanumeric segments separated by periods, and contain no underscores
+/// in their last two segments. Unless `allow_short` is set, at least one period is required.
+fn is_valid_domain(text: &str, allow_short: bool) -> bool {
+    let len = text
+        .find(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_' || c == '.'))
+        .unwrap_or(text.len());
+    let segments: Vec<&str> = text[..len].trim_end_matches('.').split('.').collect();
+    !segments.iter().any(|s| s.is_empty())
+        && (allow_short || segments.len() > 1)
+        && !segments.iter().rev().take(2).any(|s| s.contains('_'))
+}
+
+/// Removes the trailing punctuation, unbalanced closing parentheses and entity references,
+/// which are no part of autolinks
+fn trim_autolink(url: &str) -> &str {
+    let mut url = url;
+    loop {
+        let trimmed = url.trim_end_matches(['?', '!', '.', ',', ':', '*', '_', '~', '\'', '"']);
+        let trimmed = if trimmed.ends_with(')')
+            && trimmed.matches(')').count() > trimmed.matches('(').count()
+        {
+            &trimmed[..trimmed.len() - 1]
+        } else if let Some(without_semicolon) = trimmed.strip_suffix(';') {
+            let name_start =
+                without_semicolon.trim_end_matches(|c: char| c.is_ascii_alphanumeric());
+            match name_start.strip_suffix('&') {
+                Some(entity_start) if name_start.len() < without_semicolon.len() => entity_start,
+                _ => without_semicolon,
+            }
+        } else {
+            trimmed
+        };
+        if trimmed.len() == url.len() {
+            return url;
+        }
+        url = trimmed;
+    }
+}
+
+/// A reference definition like `[label]: url` or a footnote definition like `[^1]: text`
+struct Definition {
+    label: String,
+    target: String,
+    /// Byte index of the definition
+    start: usize,
+    /// Byte range of the destination
+    target_range: Range<usize>,
+}
+
+impl Definition {
+    /// The definition of the footnote `^label` at byte index `start`
+    fn footnote(label: &str, start: usize) -> Self {
+        let label = format!("^{label}");
+        Self {
+            target: label.clone(),
+            start,
+            // Behind the opening `[`
+            target_range: start + 1..start + 1 + label.len(),
+            label,
+        }
+    }
+}
+
+/// A reference with its byte index, the byte range of its label and the label
+type Reference = (usize, Range<usize>, String);
+
+/// A reference to a label which is not defined
+fn undefined_reference(text: &str, broken_link: &BrokenLink) -> Reference {
+    let span = broken_link.span.clone();
+    let label = reference_label(broken_link.link_type, &text[span.clone()]).map_or_else(
+        || span.clone(),
+        |l| span.start + l.start..span.start + l.end,
+    );
+    (span.start, label, broken_link.reference.to_string())
+}
+
+/// The byte ranges of the destinations of the definitions by their normalized label
+fn destinations(definitions: &[Definition]) -> HashMap<String, Range<usize>> {
+    definitions
+        .iter()
+        .map(|d| (normalize_label(&d.label), d.target_range.clone()))
+        .collect()
+}
+
+/// All reference definitions of `text`
+fn definitions(text: &str, reference_definitions: &RefDefs) -> Vec<Definition> {
+    reference_definitions
+        .iter()
+        .map(|(label, definition)| {
+            let span = definition.span.clone();
+            let target_range = definition_destination(&text[span.clone()]).map_or_else(
+                || span.clone(),
+                |range| span.start + range.start..span.start + range.end,
+            );
+            Definition {
+                label: label.to_string(),
+                target: definition.dest.to_string(),
+                start: span.start,
+                target_range,
+            }
+        })
+        .collect()
+}
+
+/// The undefined references and footnotes, and the unused definitions with their byte index
+/// and the byte range of their target, ordered by position
+fn reference_issues(
+    undefined_references: Vec<Reference>,
+    footnote_references: Vec<Reference>,
+    definitions: Vec<Definition>,
+    used_labels: &HashSet<String>,
+) -> Vec<(usize, Range<usize>, String, LinkIssue)> {
+    let defined_labels: HashSet<String> = definitions
+        .iter()
+        .map(|d| normalize_label(&d.label))
+        .collect();
+    let undefined_footnotes = footnote_references
+        .into_iter()
+        .filter(|(_, _, label)| !defined_labels.contains(&normalize_label(label)));
+    let mut issues: Vec<(usize, Range<usize>, String, LinkIssue)> = undefined_references
+        .into_iter()
+        .chain(undefined_footnotes)
+        .map(|(idx, range, label)| {
+            (
+                idx,
+                range,
+                label.clone(),
+                LinkIssue::UndefinedReference(label),
+            )
+        })
+        .collect();
+    issues.extend(
+        definitions
+            .into_iter()
+            .filter(|d| !used_labels.contains(&normalize_label(&d.label)))
+            .map(|d| {
+                let issue = LinkIssue::UnusedDefinition(d.label);
+                (d.start, d.target_range, d.target, issue)
+            }),
+    );
+    issues.sort_by_key(|(idx, ..)| *idx);
+    issues
+}
+
+/// The byte range of the label of reference-style links like `[text][label]`, `[label][]`
+/// or `[label]` within their source
+fn reference_label(link_type: LinkType, source: &str) -> Option<Range<usize>> {
+    let offset = usize::from(source.starts_with('!'));
+    let source = &source[offset..];
+    let label = match link_type {
+        LinkType::Reference | LinkType::ReferenceUnknown => {
+            let end = source.strip_suffix(']')?.len();
+            source[..end].rfind('[').map(|start| start + 1..end)
+        }
+        // The source range of collapsed links may not cover the trailing `[]`
+        LinkType::Collapsed
+        | LinkType::CollapsedUnknown
+        | LinkType::Shortcut
+        | LinkType::ShortcutUnknown => {
+            let source = source.strip_suffix("[]").unwrap_or(source);
+            let end = source.strip_suffix(']')?.len();
+            source.starts_with('[').then_some(1..end)
+        }
+        LinkType::Inline | LinkType::Autolink | LinkType::Email => None,
+    }?;
+    Some(offset + label.start..offset + label.end)
+}
+
+/// Labels match case-insensitively, with white-space collapsed
+fn normalize_label(label: &str) -> String {
+    label
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::link_extractors::link_extractor::span_texts;
     use ntest::test_case;
 
     #[test]
     fn inline_no_link() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = "]This is not a () link](! has no title attribute.";
         let result = le.find_links(input);
         assert!(result.is_empty());
@@ -104,7 +651,7 @@ mod tests {
 
     #[test]
     fn commented_link() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = "]This is not a () <!--[link](link)-->.";
         let result = le.find_links(input);
         assert!(result.is_empty());
@@ -112,7 +659,7 @@ mod tests {
 
     #[test]
     fn nested_links() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input =
             "\n\r\t\n[![](http://meritbadge.herokuapp.com/mlc)](https://crates.io/crates/mlc)";
         let result = le.find_links(input);
@@ -120,20 +667,21 @@ mod tests {
             target: "http://meritbadge.herokuapp.com/mlc".to_string(),
             line: 3,
             column: 2,
-            source: "".to_string(),
+            kind: LinkKind::Image,
+            ..MarkupLink::default()
         };
         let link = MarkupLink {
             target: "https://crates.io/crates/mlc".to_string(),
             line: 3,
             column: 1,
-            source: "".to_string(),
+            ..MarkupLink::default()
         };
-        assert_eq!(vec![img, link], result);
+        assert_eq!(vec![img.spanned(input), link.spanned(input)], result);
     }
 
     #[test]
     fn link_escaped() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = "This is not a \\[link\\](random_link).";
         let result = le.find_links(input);
         assert!(result.is_empty());
@@ -141,7 +689,7 @@ mod tests {
 
     #[test]
     fn link_in_headline() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = "  # This is a [link](http://example.net/).";
         let result = le.find_links(input);
         assert_eq!(result[0].column, 15);
@@ -149,7 +697,7 @@ mod tests {
 
     #[test]
     fn no_link_colon() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = "This is not a [link]:bla.";
         let result = le.find_links(input);
         assert!(result.is_empty());
@@ -157,7 +705,7 @@ mod tests {
 
     #[test]
     fn inline_code() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = " `[code](http://example.net/)`, no link!.";
         let result = le.find_links(input);
         assert!(result.is_empty());
@@ -165,35 +713,37 @@ mod tests {
 
     #[test]
     fn link_near_inline_code() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = " `bug` [code](http://example.net/), link!.";
         let result = le.find_links(input);
         let expected = MarkupLink {
             target: "http://example.net/".to_string(),
             line: 1,
             column: 8,
-            source: "".to_string(),
+            text: Some("code".to_string()),
+            ..MarkupLink::default()
         };
-        assert_eq!(vec![expected], result);
+        assert_eq!(vec![expected.spanned(input)], result);
     }
 
     #[test]
     fn link_very_near_inline_code() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = "`bug`[code](http://example.net/)";
         let result = le.find_links(input);
         let expected = MarkupLink {
             target: "http://example.net/".to_string(),
             line: 1,
             column: 6,
-            source: "".to_string(),
+            text: Some("code".to_string()),
+            ..MarkupLink::default()
         };
-        assert_eq!(vec![expected], result);
+        assert_eq!(vec![expected.spanned(input)], result);
     }
 
     #[test]
     fn code_block() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = " ``` js\n[code](http://example.net/)```, no link!.";
         let result = le.find_links(input);
         assert!(result.is_empty());
@@ -201,7 +751,7 @@ mod tests {
 
     #[test]
     fn html_code_block() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = "<script>\n[code](http://example.net/)</script>, no link!.";
         let result = le.find_links(input);
         assert!(result.is_empty());
@@ -209,21 +759,22 @@ mod tests {
 
     #[test]
     fn escaped_code_block() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = "   klsdjf \\`[escape](http://example.net/)\\`, no link!.";
         let result = le.find_links(input);
         let expected = MarkupLink {
             target: "http://example.net/".to_string(),
             line: 1,
             column: 13,
-            source: "".to_string(),
+            text: Some("escape".to_string()),
+            ..MarkupLink::default()
         };
-        assert_eq!(vec![expected], result);
+        assert_eq!(vec![expected.spanned(input)], result);
     }
 
     #[test]
     fn link_in_code_block() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let input = "```\n[only code](http://example.net/)\n```.";
         let result = le.find_links(input);
         assert!(result.is_empty());
@@ -231,7 +782,7 @@ mod tests {
 
     #[test]
     fn image_reference() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let link_str = "http://example.net/";
         let input = format!("\n\nBla ![This is an image link]({})", link_str);
         let result = le.find_links(&input);
@@ -239,14 +790,16 @@ mod tests {
             target: link_str.to_string(),
             line: 3,
             column: 5,
-            source: "".to_string(),
+            kind: LinkKind::Image,
+            text: Some("This is an image link".to_string()),
+            ..MarkupLink::default()
         };
-        assert_eq!(vec![expected], result);
+        assert_eq!(vec![expected.spanned(&input)], result);
     }
 
     #[test]
     fn link_no_title() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let link_str = "http://example.net/";
         let input = format!("[This link]({}) has no title attribute.", link_str);
         let result = le.find_links(&input);
@@ -254,14 +807,15 @@ mod tests {
             target: link_str.to_string(),
             line: 1,
             column: 1,
-            source: "".to_string(),
+            text: Some("This link".to_string()),
+            ..MarkupLink::default()
         };
-        assert_eq!(vec![expected], result);
+        assert_eq!(vec![expected.spanned(&input)], result);
     }
 
     #[test]
     fn link_with_title() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let link_str = "http://example.net/";
         let input = format!("\n123[This is a link]({} \"with title\") oh yea.", link_str);
         let result = le.find_links(&input);
@@ -269,26 +823,33 @@ mod tests {
             target: link_str.to_string(),
             line: 2,
             column: 4,
-            source: "".to_string(),
+            text: Some("This is a link".to_string()),
+            title: Some("with title".to_string()),
+            ..MarkupLink::default()
         };
-        assert_eq!(vec![expected], result);
+        assert_eq!(vec![expected.spanned(&input)], result);
     }
 
-    #[test_case("<http://example.net/>", 1)]
-    // TODO GitHub Link style support
-    //#[test_case("This is a short link http://example.net/", 22)]
-    //#[test_case("http://example.net/", 1)]
-    #[test_case("This is a short link <http://example.net/>", 22)]
-    fn inline_link(input: &str, column: usize) {
-        let le = MarkdownLinkExtractor();
+    #[test_case("<http://example.net/>", 1, "http://example.net/")]
+    #[test_case("This is a short link http://example.net/", 22, "")]
+    #[test_case("http://example.net/", 1, "")]
+    #[test_case(
+        "This is a short link <http://example.net/>",
+        22,
+        "http://example.net/"
+    )]
+    fn inline_link(input: &str, column: usize, text: &str) {
+        let le = MarkdownLinkExtractor::default();
         let result = le.find_links(input);
         let expected = MarkupLink {
             target: "http://example.net/".to_string(),
             line: 1,
             column,
-            source: "".to_string(),
+            kind: LinkKind::Autolink,
+            text: Some(text.to_string()).filter(|t| !t.is_empty()),
+            ..MarkupLink::default()
         };
-        assert_eq!(vec![expected], result);
+        assert_eq!(vec![expected.spanned(input)], result);
     }
 
     #[test_case(
@@ -300,59 +861,70 @@ mod tests {
         test_name = "html_link_no_target"
     )]
     fn html_link(input: &str) {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let result = le.find_links(input);
         let expected = MarkupLink {
             target: "http://example.net/".to_string(),
             line: 1,
             column: 1,
-            source: "".to_string(),
+            kind: LinkKind::Html,
+            attribute: Some("href".to_string()),
+            ..MarkupLink::default()
         };
-        assert_eq!(vec![expected], result);
+        assert_eq!(vec![expected.spanned(input)], result);
     }
 
     #[test]
     fn html_link_ident() {
-        let le = MarkdownLinkExtractor();
-        let result = le.find_links("123<a href=\"http://example.net/\"> link text</a>");
+        let le = MarkdownLinkExtractor::default();
+        let input = "123<a href=\"http://example.net/\"> link text</a>";
+        let result = le.find_links(input);
         let expected = MarkupLink {
             target: "http://example.net/".to_string(),
             line: 1,
             column: 4,
-            source: "".to_string(),
+            kind: LinkKind::Html,
+            attribute: Some("href".to_string()),
+            ..MarkupLink::default()
         };
-        assert_eq!(vec![expected], result);
+        assert_eq!(vec![expected.spanned(input)], result);
     }
 
     #[test]
     fn html_link_new_line() {
-        let le = MarkdownLinkExtractor();
-        let result = le.find_links("\n123<a href=\"http://example.net/\"> link text</a>");
+        let le = MarkdownLinkExtractor::default();
+        let input = "\n123<a href=\"http://example.net/\"> link text</a>";
+        let result = le.find_links(input);
         let expected = MarkupLink {
             target: "http://example.net/".to_string(),
             line: 2,
             column: 4,
-            source: "".to_string(),
+            kind: LinkKind::Html,
+            attribute: Some("href".to_string()),
+            ..MarkupLink::default()
         };
-        assert_eq!(vec![expected], result);
+        assert_eq!(vec![expected.spanned(input)], result);
     }
 
     #[test]
     fn raw_html_issue_31() {
-        let le = MarkdownLinkExtractor();
-        let result = le.find_links("Some text <a href=\"some_url\">link text</a> more text.");
+        let le = MarkdownLinkExtractor::default();
+        let input = "Some text <a href=\"some_url\">link text</a> more text.";
+        let result = le.find_links(input);
         let expected = MarkupLink {
             target: "some_url".to_string(),
             line: 1,
             column: 11,
-            source: "".to_string(),
+            kind: LinkKind::Html,
+            attribute: Some("href".to_string()),
+            ..MarkupLink::default()
         };
-        assert_eq!(vec![expected], result);
+        assert_eq!(vec![expected.spanned(input)], result);
     }
 
     #[test]
     fn referenced_link() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let link_str = "http://example.net/";
         let input = format!(
             "This is [an example][arbitrary case-insensitive reference text] reference-style link.\n\n[Arbitrary CASE-insensitive reference text]: {}",
@@ -363,29 +935,360 @@ mod tests {
             target: link_str.to_string(),
             line: 1,
             column: 9,
-            source: "".to_string(),
+            kind: LinkKind::Reference,
+            text: Some("an example".to_string()),
+            ..MarkupLink::default()
         };
-        assert_eq!(vec![expected], result);
+        assert_eq!(vec![expected.spanned(&input)], result);
     }
 
     #[test]
     fn referenced_link_tag_only() {
-        let le = MarkdownLinkExtractor();
+        let le = MarkdownLinkExtractor::default();
         let link_str = "http://example.net/";
         let input = format!(
             "Foo Bar\n\n[Arbitrary CASE-insensitive reference text]: {}",
             link_str
         );
         let result = le.find_links(&input);
-        assert_eq!(0, result.len());
+        let expected = MarkupLink {
+            target: link_str.to_string(),
+            line: 3,
+            column: 1,
+            issue: Some(LinkIssue::UnusedDefinition(
+                "Arbitrary CASE-insensitive reference text".to_string(),
+            )),
+            kind: LinkKind::Reference,
+            ..MarkupLink::default()
+        };
+        assert_eq!(vec![expected.spanned(&input)], result);
     }
 
     #[test]
     fn referenced_link_no_tag_only() {
-        let le = MarkdownLinkExtractor();
-        let input = "[link][reference]";
+        let le = MarkdownLinkExtractor::default();
+        let input = "Foo\n\nSee [link][reference]";
+        let result = le.find_links(input);
+        let expected = MarkupLink {
+            target: "reference".to_string(),
+            line: 3,
+            column: 5,
+            issue: Some(LinkIssue::UndefinedReference("reference".to_string())),
+            kind: LinkKind::Reference,
+            ..MarkupLink::default()
+        };
+        assert_eq!(vec![expected.spanned(input)], result);
+    }
+
+    #[test_case("[Foo][]\n\n[foo]: http://example.net/")]
+    #[test_case("![Foo][]\n\n[foo]: http://example.net/")]
+    #[test_case("[Foo]\n\n[foo]: http://example.net/")]
+    #[test_case("[bar][FOO]\n\n[foo]: http://example.net/")]
+    fn referenced_link_used(input: &str) {
+        let le = MarkdownLinkExtractor::default();
+        let result = le.find_links(input);
+        assert_eq!(1, result.len());
+        assert_eq!(None, result[0].issue);
+    }
+
+    #[test_case("- [ ] task")]
+    #[test_case("array[0]")]
+    fn shortcut_without_definition(input: &str) {
+        let le = MarkdownLinkExtractor::default();
+        let result = le.find_links(input);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn collapsed_reference_undefined() {
+        let le = MarkdownLinkExtractor::default();
+        let result = le.find_links("[missing][]");
+        assert_eq!(1, result.len());
+        assert_eq!(
+            Some(LinkIssue::UndefinedReference("missing".to_string())),
+            result[0].issue
+        );
+    }
+
+    #[test_case("Visit https://example.net.", "https://example.net")]
+    #[test_case("Visit www.example.net!", "http://www.example.net")]
+    #[test_case("(see https://example.net/a_(b))", "https://example.net/a_(b)")]
+    #[test_case("(see https://example.net/a)", "https://example.net/a")]
+    #[test_case("https://example.net/?q=1&amp;", "https://example.net/?q=1")]
+    #[test_case("https://example.net/a;", "https://example.net/a")]
+    #[test_case("*www.example.net/a*", "http://www.example.net/a")]
+    #[test_case("_https://example.net/a_b_", "https://example.net/a_b")]
+    #[test_case("https://example.net/a<b", "https://example.net/a")]
+    #[test_case("http://localhost:8080/", "http://localhost:8080/")]
+    #[test_case("Ümlaut https://example.net/ü", "https://example.net/ü")]
+    fn autolink(input: &str, target: &str) {
+        let le = MarkdownLinkExtractor::default();
+        let result = le.find_links(input);
+        assert_eq!(1, result.len());
+        assert_eq!(target, result[0].target);
+        let start = input.find(['h', 'w']).unwrap();
+        assert_eq!(input[..start].chars().count() + 1, result[0].column);
+    }
+
+    #[test_case("xwww.example.net")]
+    #[test_case("xhttps://example.net")]
+    #[test_case("www.")]
+    #[test_case("www.example_site.net")]
+    #[test_case("https://")]
+    #[test_case("`https://example.net`")]
+    #[test_case("    https://example.net")]
+    #[test_case("```\nhttps://example.net\n```")]
+    fn no_autolink(input: &str) {
+        let le = MarkdownLinkExtractor::default();
+        let result = le.find_links(input);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn autolink_in_link_text() {
+        let le = MarkdownLinkExtractor::default();
+        let result = le.find_links("[https://example.net](https://example.org)");
+        assert_eq!(1, result.len());
+        assert_eq!("https://example.org", result[0].target);
+    }
+
+    #[test]
+    fn autolinks_position() {
+        let le = MarkdownLinkExtractor::default();
+        let input = "# Links\n\nA *b* www.example.net and\nhttps://example.org/";
+        let result = le.find_links(input);
+        let positions: Vec<(usize, usize, &str)> = result
+            .iter()
+            .map(|l| (l.line, l.column, l.target.as_str()))
+            .collect();
+        assert_eq!(
+            vec![
+                (3, 7, "http://www.example.net"),
+                (4, 1, "https://example.org/")
+            ],
+            positions
+        );
+    }
+
+    #[test_case("[[Page Name]]", "[[Page Name]]")]
+    #[test_case("See [[Page|the page]].", "[[Page]]")]
+    #[test_case("[[ Page#Heading ]]", "[[Page#Heading]]")]
+    #[test_case("[[#Heading]]", "[[#Heading]]")]
+    #[test_case("[[Page#^block-1]]", "[[Page#^block-1]]")]
+    #[test_case("![[embed.png]]", "![[embed.png]]")]
+    #[test_case("* [[folder/Page_1]]", "[[folder/Page_1]]")]
+    fn wiki_link(input: &str, target: &str) {
+        let le = MarkdownLinkExtractor {
+            wiki_links: true,
+            ..Default::default()
+        };
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![target],
+            result.iter().map(|l| &l.target).collect::<Vec<_>>()
+        );
+    }
+
+    #[test_case("[[Page]]", "inline", "")]
+    #[test_case("See [[Page|the page]].", "inline", "the page")]
+    #[test_case("![[embed.png|Embedded]]", "image", "Embedded")]
+    #[test_case("[Page][page]\n\n[page]: ./page.md", "reference", "Page")]
+    #[test_case("![The *logo*][logo]\n\n[logo]: ./logo.png", "image", "The logo")]
+    #[test_case("[`code` and\ntext](./a.md)", "inline", "code and text")]
+    #[test_case("<https://example.net>", "autolink", "https://example.net")]
+    #[test_case("Visit www.example.net", "autolink", "")]
+    fn link_kind_and_text(input: &str, kind: &str, text: &str) {
+        let le = MarkdownLinkExtractor {
+            wiki_links: true,
+            ..Default::default()
+        };
+        let result = le.find_links(input);
+        assert_eq!(kind.parse::<LinkKind>().unwrap(), result[0].kind);
+        assert_eq!(
+            Some(text.to_string()).filter(|t| !t.is_empty()),
+            result[0].text
+        );
+    }
+
+    #[test_case("[[Page]]", false)]
+    #[test_case("`[[Page]]`", true)]
+    #[test_case("    [[Page]]", true)]
+    #[test_case("[[]]", true)]
+    #[test_case("[[Page\nName]]", true)]
+    fn no_wiki_link(input: &str, wiki_links: bool) {
+        let le = MarkdownLinkExtractor {
+            wiki_links,
+            ..Default::default()
+        };
+        let result = le.find_links(input);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn wiki_links_position() {
+        let le = MarkdownLinkExtractor {
+            wiki_links: true,
+            ..Default::default()
+        };
+        let input = "# Notes\n\nSee https://example.net and [[Page]]\n![[a.png]]";
+        let result = le.find_links(input);
+        let positions: Vec<(usize, usize, &str)> = result
+            .iter()
+            .map(|l| (l.line, l.column, l.target.as_str()))
+            .collect();
+        assert_eq!(
+            vec![
+                (3, 5, "https://example.net"),
+                (3, 29, "[[Page]]"),
+                (4, 1, "![[a.png]]")
+            ],
+            positions
+        );
+    }
+
+    #[test]
+    fn footnotes() {
+        let le = MarkdownLinkExtractor::default();
+        let input =
+            "Text[^1] and [^Note] and [^missing].\n\n[^1]: One\n\n[^note]: Two\n\n[^unused]: Three";
+        let result = le.find_links(input);
+        let issues: Vec<(usize, usize, Option<LinkIssue>)> = result
+            .into_iter()
+            .map(|l| (l.line, l.column, l.issue))
+            .collect();
+        assert_eq!(
+            vec![
+                (
+                    1,
+                    26,
+                    Some(LinkIssue::UndefinedReference("^missing".to_string()))
+                ),
+                (
+                    7,
+                    1,
+                    Some(LinkIssue::UnusedDefinition("^unused".to_string()))
+                )
+            ],
+            issues
+        );
+    }
+
+    #[test]
+    fn link_in_table() {
+        let le = MarkdownLinkExtractor::default();
+        let input = "| a | b |\n|---|---|\n| [l](x.md) | ~~old~~ |";
+        let result = le.find_links(input);
+        assert_eq!(1, result.len());
+        assert_eq!("x.md", result[0].target);
+        assert_eq!((3, 3), (result[0].line, result[0].column));
+    }
+
+    #[test_case("Visit https://example.net")]
+    #[test_case("Text[^1]")]
+    fn common_mark_without_extensions(input: &str) {
+        let le = MarkdownLinkExtractor {
+            dialect: MarkdownDialect::CommonMark,
+            ..Default::default()
+        };
+        let result = le.find_links(input);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn front_matter_skipped() {
+        let le = MarkdownLinkExtractor::default();
+        let input = "---\ntitle: \"[x](title.md)\"\nimage: ./img.png\n---\n\n[a](b.md)";
+        let result = le.find_links(input);
+        let expected = MarkupLink {
+            target: "b.md".to_string(),
+            line: 6,
+            column: 1,
+            text: Some("a".to_string()),
+            ..MarkupLink::default()
+        };
+        assert_eq!(vec![expected.spanned(input)], result);
+    }
+
+    #[test]
+    fn front_matter_keys() {
+        let le = MarkdownLinkExtractor {
+            front_matter_keys: vec!["image".to_string(), "redirect_from".to_string()],
+            ..Default::default()
+        };
+        let input = "---\nimage: ./img.png\nredirect_from:\n  - /old/\n---\n[a](b.md)";
+        let result = le.find_links(input);
+        let positions: Vec<(usize, usize, &str)> = result
+            .iter()
+            .map(|l| (l.line, l.column, l.target.as_str()))
+            .collect();
+        assert_eq!(
+            vec![(6, 1, "b.md"), (2, 8, "./img.png"), (4, 5, "/old/")],
+            positions
+        );
+    }
+
+    #[test_case("[a](b.md)", "b.md")]
+    #[test_case("[a](<b c.md> \"title\")", "b c.md")]
+    #[test_case("[a]( b.md 'title' )", "b.md")]
+    #[test_case("[a](b_(c).md)", "b_(c).md")]
+    #[test_case("![😀](img.png)", "img.png")]
+    #[test_case("[a [b]](c.md)", "c.md")]
+    #[test_case("[`]` \\]](c.md)", "c.md")]
+    #[test_case("[a][r]\n\n[r]: https://example.net \"title\"", "https://example.net")]
+    #[test_case("[r][]\n\n[R]:\n  <b c.md>", "b c.md")]
+    #[test_case("[r]\n\n[r]: b.md", "b.md")]
+    #[test_case("<https://example.net>", "https://example.net")]
+    #[test_case("<me@example.net>", "me@example.net")]
+    #[test_case("See https://example.net/.", "https://example.net/")]
+    #[test_case("See www.example.net.", "www.example.net")]
+    #[test_case("[a][missing]", "missing")]
+    #[test_case("Note[^1]", "^1")]
+    #[test_case("<a href=\"b.md\">a</a>", "b.md")]
+    #[test_case("* item\n\n  <p>\n  <img src=\"a.png\">\n  </p>", "a.png")]
+    fn span(input: &str, raw: &str) {
+        let le = MarkdownLinkExtractor::default();
         let result = le.find_links(input);
-        assert_eq!(0, result.len());
-        // TODO: Check broken links
+        assert_eq!(raw, span_texts(input, &result)[0]);
+    }
+
+    #[test]
+    fn unused_definition_span() {
+        let le = MarkdownLinkExtractor::default();
+        let input = "[a]\n\n[a]: b.md\n[unused]: c.md";
+        let result = le.find_links(input);
+        assert_eq!(vec!["b.md", "c.md"], span_texts(input, &result));
+    }
+
+    #[test]
+    fn wiki_link_and_front_matter_spans() {
+        let le = MarkdownLinkExtractor {
+            front_matter_keys: vec!["image".to_string()],
+            wiki_links: true,
+            ..Default::default()
+        };
+        let input = "---\nimage: ./img.png\n---\nSee [[Page|alias]]";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec!["./img.png", "[[Page|alias]]"],
+            span_texts(input, &result)
+        );
+    }
+
+    #[test]
+    fn encoding_aware_positions() {
+        let le = MarkdownLinkExtractor::default();
+        let result = le.find_links("😀 ü [a](b.md)");
+        assert_eq!((1, 5), (result[0].line, result[0].column));
+        let start = result[0].span.start;
+        assert_eq!(
+            (1, 13, 9, 10),
+            (
+                start.line,
+                start.byte_column,
+                start.char_column,
+                start.utf16_column
+            )
+        );
+        assert_eq!(17, result[0].span.end.byte_column);
     }
 }
@@ -0,0 +1,429 @@
+use super::front_matter::FrontMatter;
+use super::html_link_extractor::attribute_kind;
+use crate::link_extractors::link_extractor::line_column_from_idx;
+use crate::link_extractors::link_extractor::trimmed_range;
+use crate::link_extractors::link_extractor::LinkExtractor;
+use crate::link_extractors::link_extractor::LinkKind;
+use crate::link_extractors::link_extractor::MarkupLink;
+use crate::link_extractors::link_extractor::Position;
+use crate::link_extractors::link_extractor::Span;
+use crate::link_extractors::markdown_link_extractor::MarkdownLinkExtractor;
+use regex::Regex;
+use std::ops::Range;
+
+/// Extracts the links of MDX files, which mix markdown with JSX elements,
+/// JavaScript expressions and `import` or `export` statements
+#[derive(Default)]
+pub struct MdxLinkExtractor(pub MarkdownLinkExtractor);
+
+impl LinkExtractor for MdxLinkExtractor {
+    fn find_links(&self, text: &str) -> Vec<MarkupLink> {
+        let document = MdxDocument::parse(text);
+        let mut result = self.0.find_links(&document.markdown);
+        let line_starts: Vec<usize> = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(idx, _)| idx + 1))
+            .collect();
+        // The byte index into `text` of a position within the markdown
+        let source_offset = |position: Position| {
+            let line = position.line - 1;
+            line_starts.get(line).map_or(text.len(), |start| {
+                start + document.indents.get(line).unwrap_or(&0) + position.byte_column - 1
+            })
+        };
+        for link in &mut result {
+            link.column += document.indents.get(link.line - 1).unwrap_or(&0);
+            let range = source_offset(link.span.start)..source_offset(link.span.end);
+            link.span = Span::new(text, range);
+        }
+        for (range, target, prop) in document.links {
+            let (line, column) = line_column_from_idx(text, range.start);
+            let kind = prop.as_ref().map_or(LinkKind::Inline, |(element, name)| {
+                attribute_kind(element, name)
+            });
+            result.push(MarkupLink {
+                target,
+                line,
+                column,
+                kind,
+                attribute: prop.map(|(_element, name)| name),
+                span: Span::new(text, range),
+                ..MarkupLink::default()
+            });
+        }
+        result.sort_by_key(|link| (link.line, link.column));
+        result
+    }
+}
+
+/// An MDX document split into its markdown and the links of its JSX and imports
+pub struct MdxDocument {
+    /// The markdown with JSX, expressions and ES module statements replaced by white-space.
+    /// Indentation is removed, because MDX has no indented code blocks.
+    pub markdown: String,
+    /// The bytes of indentation removed from each line
+    indents: Vec<usize>,
+    /// The `to`, `href` and `src` props of JSX elements, and the local modules of imports,
+    /// with their byte range and the element and name of props
+    links: Vec<(Range<usize>, String, Option<Prop>)>,
+}
+
+/// The element and the name of a JSX prop, like `("Link", "to")`
+type Prop = (String, String);
+
+impl MdxDocument {
+    pub fn parse(text: &str) -> Self {
+        let start = FrontMatter::find(text).map_or(0, |f| f.end());
+        let mut masked: Vec<u8> = text.as_bytes().to_vec();
+        let mut links = Vec::new();
+        let mut idx = start;
+        while idx < text.len() {
+            let at_line_start = idx == start || text.as_bytes()[idx - 1] == b'\n';
+            if at_line_start {
+                if let Some(end) = fenced_code_end(text, idx) {
+                    idx = end;
+                    continue;
+                }
+                if let Some(end) = esm_end(text, idx) {
+                    links.extend(
+                        imports(&text[idx..end])
+                            .map(|(r, v)| (idx + r.start..idx + r.end, v, None)),
+                    );
+                    blank(&mut masked, idx..end);
+                    idx = end;
+                    continue;
+                }
+            }
+            let end = match text.as_bytes()[idx] {
+                b'`' => Some(code_span_end(text, idx)),
+                b'{' => expression_end(text, idx).inspect(|end| {
+                    if !is_heading_id(&text[idx..*end]) {
+                        blank(&mut masked, idx..*end);
+                    }
+                }),
+                b'<' => tag_end(text, idx).inspect(|end| {
+                    let tag = &text[idx..*end];
+                    links.extend(
+                        props(tag).map(|(r, v, p)| (idx + r.start..idx + r.end, v, Some(p))),
+                    );
+                    blank(&mut masked, idx..*end);
+                }),
+                b'\\' => text[idx + 1..]
+                    .chars()
+                    .next()
+                    .map(|c| idx + 1 + c.len_utf8()),
+                _ => None,
+            };
+            idx = end.unwrap_or_else(|| idx + text[idx..].chars().next().map_or(1, char::len_utf8));
+        }
+        let masked = String::from_utf8(masked).expect("Only ASCII characters were replaced");
+        let (markdown, indents) = dedent(&masked, start);
+        Self {
+            markdown,
+            indents,
+            links,
+        }
+    }
+}
+
+/// Replaces all but line breaks with spaces
+fn blank(bytes: &mut [u8], range: Range<usize>) {
+    for byte in &mut bytes[range] {
+        if *byte != b'\n' {
+            *byte = b' ';
+        }
+    }
+}
+
+/// Removes the indentation of all lines behind `start`
+fn dedent(text: &str, start: usize) -> (String, Vec<usize>) {
+    let mut markdown = String::with_capacity(text.len());
+    let mut indents = Vec::new();
+    let mut line_start = 0;
+    for line in text.split_inclusive('\n') {
+        let content = if line_start < start {
+            line
+        } else {
+            line.trim_start_matches([' ', '\t'])
+        };
+        indents.push(line.len() - content.len());
+        markdown.push_str(content);
+        line_start += line.len();
+    }
+    (markdown, indents)
+}
+
+/// The byte index behind a fenced code block starting at `idx`, which is left as it is
+fn fenced_code_end(text: &str, idx: usize) -> Option<usize> {
+    let first_line = text[idx..].split_inclusive('\n').next()?;
+    let fence = first_line.trim_start();
+    let fence_char = fence.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let fence_len = fence.len() - fence.trim_start_matches(fence_char).len();
+    if fence_len < 3 {
+        return None;
+    }
+    let mut end = idx + first_line.len();
+    for line in text[end..].split_inclusive('\n') {
+        end += line.len();
+        let closing = line.trim();
+        if closing.len() >= fence_len && closing.chars().all(|c| c == fence_char) {
+            break;
+        }
+    }
+    Some(end)
+}
+
+/// The byte index behind `import` or `export` statements starting at `idx`,
+/// which last until the next blank line
+fn esm_end(text: &str, idx: usize) -> Option<usize> {
+    let rest = &text[idx..];
+    if !(rest.starts_with("import ") || rest.starts_with("export ")) {
+        return None;
+    }
+    let mut end = idx;
+    for line in rest.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            break;
+        }
+        end += line.len();
+    }
+    Some(end)
+}
+
+/// The relative and `@site/` modules of `import` statements with their byte range.
+/// Packages like `@theme/Tabs` are resolved by the bundler and not checked.
+fn imports(esm: &str) -> impl Iterator<Item = (Range<usize>, String)> + '_ {
+    lazy_static! {
+        static ref IMPORT_REGEX: Regex =
+            Regex::new(r#"\b(?:from|import)\s*["']([^"'\n]+)["']"#).unwrap();
+    }
+    IMPORT_REGEX
+        .captures_iter(esm)
+        .filter_map(|c| c.get(1))
+        .filter(|m| {
+            ["./", "../", "@site/"]
+                .iter()
+                .any(|p| m.as_str().starts_with(p))
+        })
+        .map(|m| (m.range(), m.as_str().to_string()))
+}
+
+/// The byte index behind an inline code span starting at `idx`,
+/// which has to end before the next blank line
+fn code_span_end(text: &str, idx: usize) -> usize {
+    let rest = &text[idx..];
+    let run_len = rest.len() - rest.trim_start_matches('`').len();
+    let paragraph = rest.find("\n\n").map_or(rest, |end| &rest[..end]);
+    let mut search = run_len;
+    while let Some(found) = paragraph[search..].find('`') {
+        let run_start = search + found;
+        let run = &paragraph[run_start..];
+        let len = run.len() - run.trim_start_matches('`').len();
+        if len == run_len {
+            return idx + run_start + len;
+        }
+        search = run_start + len;
+    }
+    idx + run_len
+}
+
+/// The byte index behind a JavaScript expression like `{props.title}` starting at `idx`
+fn expression_end(text: &str, idx: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    for (i, c) in text[idx..].char_indices() {
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some(_) if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => (),
+            None => match c {
+                '"' | '\'' | '`' => quote = Some(c),
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(idx + i + 1);
+                    }
+                }
+                _ => (),
+            },
+        }
+    }
+    None
+}
+
+/// Heading ids like `{#custom-id}` are markdown, not expressions
+fn is_heading_id(expression: &str) -> bool {
+    lazy_static! {
+        static ref HEADING_ID_REGEX: Regex = Regex::new(r"^\{#[\w-]+\}$").unwrap();
+    }
+    HEADING_ID_REGEX.is_match(expression)
+}
+
+/// The byte index behind a JSX tag like `<Link to="/docs/intro">`, `</Link>` or `<>`
+/// starting at `idx`. Autolinks like `<https://example.net>` and HTML comments are no tags.
+fn tag_end(text: &str, idx: usize) -> Option<usize> {
+    lazy_static! {
+        static ref TAG_START_REGEX: Regex =
+            Regex::new(r"^</?(?:[A-Za-z][\w.-]*(?:[\s/>]|$)|>)").unwrap();
+    }
+    TAG_START_REGEX.find(&text[idx..])?;
+    let mut quote: Option<char> = None;
+    let mut pos = idx + 1;
+    while let Some(c) = text[pos..].chars().next() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => (),
+            None => match c {
+                '"' | '\'' => quote = Some(c),
+                '{' => {
+                    pos = expression_end(text, pos)?;
+                    continue;
+                }
+                '>' => return Some(pos + 1),
+                '<' => return None,
+                _ => (),
+            },
+        }
+        pos += c.len_utf8();
+    }
+    None
+}
+
+/// The `to`, `href` and `src` props of a JSX tag with a string value, with the byte range
+/// of the value and the element and name of the prop
+fn props(tag: &str) -> impl Iterator<Item = (Range<usize>, String, Prop)> + '_ {
+    lazy_static! {
+        static ref PROP_REGEX: Regex = Regex::new(
+            r#"\s(to|href|src)\s*=\s*(?:"([^"]*)"|'([^']*)'|\{\s*(?:"([^"]*)"|'([^']*)'|`([^`$]*)`)\s*\})"#
+        )
+        .unwrap();
+    }
+    let element: String = tag
+        .trim_start_matches('<')
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || ['.', '-', '_'].contains(c))
+        .collect();
+    PROP_REGEX
+        .captures_iter(tag)
+        .filter_map(|c| Some((c.iter().skip(2).flatten().next()?, c[1].to_string())))
+        .filter(|(m, _name)| !m.as_str().trim().is_empty())
+        .map(move |(m, name)| {
+            let range = trimmed_range(tag, m.range());
+            (
+                range.clone(),
+                tag[range].to_string(),
+                (element.clone(), name),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::link_extractors::link_extractor::span_texts;
+    use ntest::test_case;
+
+    fn links(input: &str) -> Vec<(usize, usize, String)> {
+        MdxLinkExtractor::default()
+            .find_links(input)
+            .into_iter()
+            .map(|l| (l.line, l.column, l.target))
+            .collect()
+    }
+
+    #[test_case("<Link to=\"/docs/intro\">Intro</Link>", 11, "/docs/intro")]
+    #[test_case("<Link to='/docs/intro' />", 11, "/docs/intro")]
+    #[test_case("<Link to={'/docs/intro'}>x</Link>", 12, "/docs/intro")]
+    #[test_case("<Link to={`/docs/intro`}>x</Link>", 12, "/docs/intro")]
+    #[test_case("<Card title=\"A > B\" href=\"./a.md\" />", 27, "./a.md")]
+    #[test_case("<img src=\"./logo.png\" alt=\"Logo\" />", 11, "./logo.png")]
+    #[test_case("<a href=\"https://example.net\">x</a>", 10, "https://example.net")]
+    #[test_case("Read <Link to=\"./a.md\">a</Link>.", 16, "./a.md")]
+    fn jsx_props(input: &str, column: usize, target: &str) {
+        assert_eq!(vec![(1, column, target.to_string())], links(input));
+    }
+
+    #[test_case("<Link to=\"/docs/intro\">Intro</Link>", "html", "to")]
+    #[test_case("<img src=\"./logo.png\" alt=\"Logo\" />", "image", "src")]
+    #[test_case("import Hello from './Hello';", "inline", "")]
+    #[test_case("![Logo](./logo.png)", "image", "")]
+    fn kinds(input: &str, kind: &str, attribute: &str) {
+        let result = MdxLinkExtractor::default().find_links(input);
+        assert_eq!(kind.parse::<LinkKind>().unwrap(), result[0].kind);
+        assert_eq!(
+            Some(attribute.to_string()).filter(|a| !a.is_empty()),
+            result[0].attribute
+        );
+    }
+
+    #[test_case("import Tabs from '@theme/Tabs';")]
+    #[test_case("import {useState} from 'react';")]
+    #[test_case("export const meta = {link: './missing.md'};")]
+    #[test_case("<Link to={docs.intro}>x</Link>")]
+    #[test_case("<Link to={`/docs/${id}`}>x</Link>")]
+    #[test_case("<Box data-to=\"./missing.md\" />")]
+    #[test_case("{/* [commented](./missing.md) */}")]
+    #[test_case("Text {['[x](./missing.md)']}")]
+    #[test_case("`<Link to=\"./missing.md\">`")]
+    #[test_case("```jsx\n<Link to=\"./missing.md\" />\n```")]
+    fn no_links(input: &str) {
+        assert!(links(input).is_empty());
+    }
+
+    #[test]
+    fn imports() {
+        let input = "import Hello from '@site/src/components/Hello';\n\
+            import Partial, {\n  Other,\n} from \"./_partial.mdx\";\n\
+            import Tabs from '@theme/Tabs';\n\n# Title";
+        assert_eq!(
+            vec![
+                (1, 20, "@site/src/components/Hello".to_string()),
+                (4, 9, "./_partial.mdx".to_string())
+            ],
+            links(input)
+        );
+    }
+
+    #[test]
+    fn markdown_in_jsx() {
+        let input = "<Tabs>\n  <TabItem value=\"a\">\n\n    See the [guide](./guide.md).\n\n  </TabItem>\n</Tabs>";
+        assert_eq!(vec![(4, 13, "./guide.md".to_string())], links(input));
+    }
+
+    #[test]
+    fn multiline_tag() {
+        let input = "<Link\n  className=\"button\"\n  to=\"/docs/intro\">\n  Docs\n</Link>";
+        assert_eq!(vec![(3, 7, "/docs/intro".to_string())], links(input));
+    }
+
+    #[test]
+    fn markdown_links() {
+        let input = "---\ntitle: <Title>\n---\n\n# Intro {#intro}\n\n[a](./a.md) and <https://example.net> and {x}[b](#intro)";
+        assert_eq!(
+            vec![
+                (7, 1, "./a.md".to_string()),
+                (7, 17, "https://example.net".to_string()),
+                (7, 46, "#intro".to_string())
+            ],
+            links(input)
+        );
+    }
+
+    #[test]
+    fn heading_id_kept() {
+        let document = MdxDocument::parse("## Title {#custom-id}\n\n{props.title}");
+        assert_eq!("## Title {#custom-id}\n\n", document.markdown);
+    }
+
+    #[test_case("<Link to=\"/docs/intro\">Intro</Link>", "/docs/intro")]
+    #[test_case("<img src={ ' /img/a.png ' } />", "/img/a.png")]
+    #[test_case("import Intro from './intro.md';", "./intro.md")]
+    #[test_case("<div>\n  [Intro](./intro.md)\n</div>", "./intro.md")]
+    #[test_case("<Tabs>\n    ü [Intro](./intro.md)\n</Tabs>", "./intro.md")]
+    fn span(input: &str, raw: &str) {
+        let result = MdxLinkExtractor::default().find_links(input);
+        assert_eq!(vec![raw], span_texts(input, &result));
+    }
+}
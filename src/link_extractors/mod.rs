@@ -1,3 +1,16 @@
+mod asciidoc_link_extractor;
+mod directives;
+pub(crate) mod front_matter;
+mod html_entities;
 mod html_link_extractor;
+pub(crate) mod html_tokenizer;
+pub(crate) mod latex_link_extractor;
 pub mod link_extractor;
 mod markdown_link_extractor;
+pub(crate) mod mdx_link_extractor;
+mod notebook_link_extractor;
+mod org_link_extractor;
+pub(crate) mod plain_link_extractor;
+mod rst_link_extractor;
+mod source_code_link_extractor;
+mod svg_link_extractor;
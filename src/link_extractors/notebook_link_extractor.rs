@@ -0,0 +1,281 @@
+use crate::link_extractors::html_link_extractor::HtmlLinkExtractor;
+use crate::link_extractors::link_extractor::LinkExtractor;
+use crate::link_extractors::link_extractor::LinkIssue;
+use crate::link_extractors::link_extractor::MarkupLink;
+use crate::link_extractors::markdown_link_extractor::MarkdownLinkExtractor;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Extracts the links of the markdown cells of Jupyter notebooks.
+/// Lines, columns and spans of the links are relative to the text of their cell,
+/// or of the HTML output they were found in, as cells are strings within the JSON file.
+pub struct NotebookLinkExtractor {
+    /// Extracts the links of markdown cells
+    pub markdown: MarkdownLinkExtractor,
+    /// Also extract the links of the HTML outputs of code cells
+    pub html_outputs: bool,
+}
+
+#[derive(Deserialize)]
+struct Notebook {
+    #[serde(default)]
+    cells: Vec<Cell>,
+}
+
+#[derive(Deserialize)]
+struct Cell {
+    #[serde(rename = "cell_type")]
+    kind: String,
+    #[serde(default)]
+    source: MultilineString,
+    #[serde(default)]
+    attachments: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    outputs: Vec<Output>,
+}
+
+#[derive(Deserialize)]
+struct Output {
+    #[serde(default)]
+    data: OutputData,
+}
+
+#[derive(Deserialize, Default)]
+struct OutputData {
+    #[serde(rename = "text/html")]
+    html: Option<MultilineString>,
+}
+
+/// Text of a notebook, either as a single string or as a list of lines
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum MultilineString {
+    Lines(Vec<String>),
+    Text(String),
+}
+
+impl Default for MultilineString {
+    fn default() -> Self {
+        Self::Text(String::new())
+    }
+}
+
+impl MultilineString {
+    fn text(&self) -> String {
+        match self {
+            Self::Lines(lines) => lines.concat(),
+            Self::Text(text) => text.clone(),
+        }
+    }
+}
+
+impl Cell {
+    /// Attachments are embedded in the notebook, so links to them are checked right away.
+    /// Returns the link if there is something left to report.
+    fn check_attachment(&self, link: MarkupLink) -> Option<MarkupLink> {
+        match link.target.strip_prefix("attachment:") {
+            Some(name) if self.attachments.contains_key(name) => None,
+            Some(name) => Some(MarkupLink {
+                issue: Some(LinkIssue::MissingAttachment(name.to_string())),
+                ..link
+            }),
+            None => Some(link),
+        }
+    }
+}
+
+impl LinkExtractor for NotebookLinkExtractor {
+    fn find_links(&self, text: &str) -> Vec<MarkupLink> {
+        let notebook: Notebook = match serde_json::from_str(text) {
+            Ok(notebook) => notebook,
+            Err(e) => {
+                warn!("Invalid notebook. JSON Error: '{}'.", e);
+                return vec![];
+            }
+        };
+
+        let mut result: Vec<MarkupLink> = Vec::new();
+        for (cell_idx, cell) in notebook.cells.iter().enumerate() {
+            let mut links = match cell.kind.as_str() {
+                "markdown" => self
+                    .markdown
+                    .find_links(&cell.source.text())
+                    .into_iter()
+                    .filter_map(|link| cell.check_attachment(link))
+                    .collect(),
+                "code" if self.html_outputs => cell
+                    .outputs
+                    .iter()
+                    .filter_map(|output| output.data.html.as_ref())
+                    .flat_map(|html| HtmlLinkExtractor().find_links(&html.text()))
+                    .collect(),
+                _ => vec![],
+            };
+            for link in &mut links {
+                link.cell = Some(cell_idx + 1);
+            }
+            result.append(&mut links);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::link_extractors::link_extractor::link;
+    use crate::link_extractors::link_extractor::LinkKind;
+    use ntest::test_case;
+
+    fn cell_link(target: &str, cell: usize, line: usize, column: usize) -> MarkupLink {
+        MarkupLink {
+            cell: Some(cell),
+            ..link(target, line, column)
+        }
+    }
+
+    const NOTEBOOK: &str = r##"{
+ "cells": [
+  {
+   "cell_type": "markdown",
+   "metadata": {},
+   "source": [
+    "# Title\n",
+    "\n",
+    "See the [docs](https://example.net/docs) and ![logo](attachment:logo.png).\n",
+    "A [broken attachment](attachment:missing.png)."
+   ],
+   "attachments": {
+    "logo.png": {
+     "image/png": "iVBORw0KGgo="
+    }
+   }
+  },
+  {
+   "cell_type": "code",
+   "execution_count": 1,
+   "metadata": {},
+   "source": ["print('[no](link.md)')"],
+   "outputs": [
+    {
+     "output_type": "display_data",
+     "data": {
+      "text/html": ["<p>\n", "<a href=\"report.html\">Report</a></p>"],
+      "text/plain": ["[no](link.md)"]
+     },
+     "metadata": {}
+    }
+   ]
+  },
+  {
+   "cell_type": "markdown",
+   "metadata": {},
+   "source": "Back to [the readme](../README.md)"
+  }
+ ],
+ "metadata": {},
+ "nbformat": 4,
+ "nbformat_minor": 5
+}"##;
+
+    #[test]
+    fn markdown_cells() {
+        let le = NotebookLinkExtractor {
+            markdown: MarkdownLinkExtractor::default(),
+            html_outputs: false,
+        };
+        let result = le.find_links(NOTEBOOK);
+        let first_cell = "# Title\n\nSee the [docs](https://example.net/docs) and ![logo](attachment:logo.png).\nA [broken attachment](attachment:missing.png).";
+        let third_cell = "Back to [the readme](../README.md)";
+        let with_text = |link: MarkupLink, text: &str| MarkupLink {
+            text: Some(text.to_string()),
+            ..link
+        };
+        let expected = vec![
+            with_text(cell_link("https://example.net/docs", 1, 3, 9), "docs").spanned(first_cell),
+            MarkupLink {
+                issue: Some(LinkIssue::MissingAttachment("missing.png".to_string())),
+                ..with_text(
+                    cell_link("attachment:missing.png", 1, 4, 3),
+                    "broken attachment",
+                )
+            }
+            .spanned(first_cell),
+            with_text(cell_link("../README.md", 3, 1, 9), "the readme").spanned(third_cell),
+        ];
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn html_outputs() {
+        let le = NotebookLinkExtractor {
+            markdown: MarkdownLinkExtractor::default(),
+            html_outputs: true,
+        };
+        let result = le.find_links(NOTEBOOK);
+        assert_eq!(4, result.len());
+        let expected = MarkupLink {
+            kind: LinkKind::Html,
+            attribute: Some("href".to_string()),
+            ..cell_link("report.html", 2, 2, 1)
+        };
+        let output = "<p>\n<a href=\"report.html\">Report</a></p>";
+        assert_eq!(expected.spanned(output), result[2]);
+    }
+
+    #[test]
+    fn markdown_options() {
+        let le = NotebookLinkExtractor {
+            markdown: MarkdownLinkExtractor {
+                wiki_links: true,
+                ..MarkdownLinkExtractor::default()
+            },
+            html_outputs: false,
+        };
+        let input = r#"{"cells": [{"cell_type": "markdown", "source": "See [[Setup]]"}]}"#;
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![("[[Setup]]".to_string(), Some(1))],
+            result
+                .into_iter()
+                .map(|l| (l.target, l.cell))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test_case("")]
+    #[test_case("no json")]
+    #[test_case("{\"cells\": 3}")]
+    fn invalid_notebook(input: &str) {
+        let le = NotebookLinkExtractor {
+            markdown: MarkdownLinkExtractor::default(),
+            html_outputs: false,
+        };
+        let result = le.find_links(input);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn empty_notebook() {
+        let le = NotebookLinkExtractor {
+            markdown: MarkdownLinkExtractor::default(),
+            html_outputs: false,
+        };
+        let result = le.find_links("{\"cells\": []}");
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn spans_relative_to_cell() {
+        let le = NotebookLinkExtractor {
+            markdown: MarkdownLinkExtractor::default(),
+            html_outputs: false,
+        };
+        let result = le.find_links(NOTEBOOK);
+        let start = result[0].span.start;
+        assert_eq!(
+            (Some(1), 3, 16),
+            (result[0].cell, start.line, start.char_column)
+        );
+    }
+}
@@ -0,0 +1,315 @@
+use crate::link_extractors::link_extractor::line_column_from_idx;
+use crate::link_extractors::link_extractor::trim_url;
+use crate::link_extractors::link_extractor::trimmed_range;
+use crate::link_extractors::link_extractor::LinkExtractor;
+use crate::link_extractors::link_extractor::LinkKind;
+use crate::link_extractors::link_extractor::MarkupLink;
+use crate::link_extractors::link_extractor::Span;
+use regex::Regex;
+use std::ops::Range;
+
+pub struct OrgLinkExtractor();
+
+lazy_static! {
+    static ref BLOCK_BEGIN_REGEX: Regex =
+        Regex::new(r"(?i)^\s*#\+begin_(src|example|export|comment)\b").unwrap();
+    // Comment lines and fixed-width areas
+    static ref VERBATIM_LINE_REGEX: Regex = Regex::new(r"^\s*(?:#(?:\s|$)|:(?:\s|$))").unwrap();
+    static ref INCLUDE_REGEX: Regex =
+        Regex::new(r#"(?im)^[ \t]*#\+include:[ \t]*(?:"([^"\n]+)"|(\S+))"#).unwrap();
+    static ref BRACKET_LINK_REGEX: Regex =
+        Regex::new(r"\[\[((?:[^\[\]\\]|\\.)+)\](?:\[([^\n]*?)\])?\]").unwrap();
+    static ref PLAIN_LINK_REGEX: Regex =
+        Regex::new(r"\b(?:https?|ftp)://[^\s()<>\[\]]+(?:\([^\s()<>\[\]]*\))?|\b(?:mailto|file):[^\s()<>\[\]]+").unwrap();
+}
+
+/// A link with its byte index, the byte range of its target, the target, kind and description
+type FoundLink = (usize, Range<usize>, String, LinkKind, Option<String>);
+
+impl LinkExtractor for OrgLinkExtractor {
+    fn find_links(&self, text: &str) -> Vec<MarkupLink> {
+        let inline_text = mask_verbatim(text);
+        let mut result: Vec<FoundLink> = Vec::new();
+        for caps in INCLUDE_REGEX.captures_iter(&inline_text) {
+            let path = caps.get(1).or_else(|| caps.get(2)).unwrap();
+            let start = caps.get(0).unwrap().start();
+            let target = file_target(path.as_str());
+            result.push((start, path.range(), target, LinkKind::Inline, None));
+        }
+        // Bracket links are masked, so they are not found again as plain links
+        let mut plain_text = inline_text.clone();
+        for caps in BRACKET_LINK_REGEX.captures_iter(&inline_text) {
+            let link = caps.get(0).unwrap();
+            plain_text.replace_range(link.range(), &" ".repeat(link.as_str().len()));
+            let target = caps[1]
+                .replace("\\[", "[")
+                .replace("\\]", "]")
+                .replace("\\\\", "\\");
+            let range = trimmed_range(text, caps.get(1).unwrap().range());
+            if let Some(target) = org_target(target.trim()) {
+                let description = caps
+                    .get(2)
+                    .map(|d| d.as_str().trim().to_string())
+                    .filter(|d| !d.is_empty());
+                result.push((link.start(), range, target, LinkKind::Inline, description));
+            }
+        }
+        for link in PLAIN_LINK_REGEX.find_iter(&plain_text) {
+            let url = trim_url(link.as_str());
+            let range = link.start()..link.start() + url.len();
+            if let Some(target) = org_target(url) {
+                result.push((link.start(), range, target, LinkKind::Autolink, None));
+            }
+        }
+
+        result.sort_by_key(|(idx, ..)| *idx);
+        result
+            .into_iter()
+            .map(|(idx, range, target, kind, description)| {
+                let (line, column) = line_column_from_idx(text, idx);
+                MarkupLink {
+                    line,
+                    column,
+                    target,
+                    kind,
+                    text: description,
+                    span: Span::new(text, range),
+                    ..MarkupLink::default()
+                }
+            })
+            .collect()
+    }
+}
+
+/// Replaces comments, fixed-width lines and the content of source, example, export and comment
+/// blocks with white-space, keeping the byte offsets of the remaining text intact.
+fn mask_verbatim(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    // The line closing the current block, like `#+end_src`
+    let mut block_end: Option<String> = None;
+    for (line_idx, line) in text.split('\n').enumerate() {
+        if line_idx > 0 {
+            result.push('\n');
+        }
+        let masked = if let Some(end) = &block_end {
+            if line.trim().to_lowercase().starts_with(end.as_str()) {
+                block_end = None;
+            }
+            true
+        } else if let Some(caps) = BLOCK_BEGIN_REGEX.captures(line) {
+            block_end = Some(format!("#+end_{}", caps[1].to_lowercase()));
+            true
+        } else {
+            VERBATIM_LINE_REGEX.is_match(line)
+        };
+
+        if masked {
+            result.push_str(&" ".repeat(line.len()));
+        } else {
+            result.push_str(line);
+        }
+    }
+    result
+}
+
+/// Maps the target of an Org link to a link target as used by the other markup types.
+/// Returns `None` for links to headings or text of the same document.
+fn org_target(target: &str) -> Option<String> {
+    lazy_static! {
+        static ref SCHEME_REGEX: Regex = Regex::new(r"^([a-zA-Z][\w+-]*):").unwrap();
+    }
+    if let Some(caps) = SCHEME_REGEX.captures(target) {
+        return match &caps[1] {
+            "file" | "file+sys" | "file+emacs" => Some(file_target(&target[caps[0].len()..])),
+            _ => Some(target.to_string()),
+        };
+    }
+    if target.starts_with('/') || target.starts_with("./") || target.starts_with("../") {
+        Some(file_target(target))
+    } else if target.starts_with('#') {
+        Some(target.to_string())
+    } else {
+        None
+    }
+}
+
+/// Converts the search option of a file link into a fragment.
+/// Line numbers become line anchors like `#L10`, custom ids are kept.
+fn file_target(target: &str) -> String {
+    match target.split_once("::") {
+        Some((path, search))
+            if !search.is_empty() && search.bytes().all(|b| b.is_ascii_digit()) =>
+        {
+            format!("{path}#L{search}")
+        }
+        Some((path, search)) if search.starts_with('#') => format!("{path}{search}"),
+        Some((path, _)) => path.to_string(),
+        None => target.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::link_extractors::link_extractor::link;
+    use crate::link_extractors::link_extractor::span_texts;
+    use ntest::test_case;
+
+    #[test]
+    fn no_link() {
+        let le = OrgLinkExtractor();
+        let input = "* Heading\n\nThis is *no* link, just /text/ and =code=.";
+        let result = le.find_links(input);
+        assert!(result.is_empty());
+    }
+
+    #[test_case(
+        "[[https://example.net/][Example]]",
+        "https://example.net/",
+        "https://example.net/",
+        1,
+        "Example"
+    )]
+    #[test_case(
+        "See [[https://example.net/]].",
+        "https://example.net/",
+        "https://example.net/",
+        5,
+        ""
+    )]
+    #[test_case("[[file:notes.org][Notes]]", "notes.org", "file:notes.org", 1, "Notes")]
+    #[test_case(
+        "[[file:notes.org::42][Notes]]",
+        "notes.org#L42",
+        "file:notes.org::42",
+        1,
+        "Notes"
+    )]
+    #[test_case(
+        "[[file:notes.org::#design][Notes]]",
+        "notes.org#design",
+        "file:notes.org::#design",
+        1,
+        "Notes"
+    )]
+    #[test_case(
+        "[[file:notes.org::*Design][Notes]]",
+        "notes.org",
+        "file:notes.org::*Design",
+        1,
+        "Notes"
+    )]
+    #[test_case(
+        "[[./images/logo.png]]",
+        "./images/logo.png",
+        "./images/logo.png",
+        1,
+        ""
+    )]
+    #[test_case(
+        "[[../README.md][ The Readme ]]",
+        "../README.md",
+        "../README.md",
+        1,
+        "The Readme"
+    )]
+    #[test_case("[[#custom-id][Section]]", "#custom-id", "#custom-id", 1, "Section")]
+    #[test_case(
+        "[[mailto:me@example.net][Mail]]",
+        "mailto:me@example.net",
+        "mailto:me@example.net",
+        1,
+        "Mail"
+    )]
+    #[test_case("Umlaut ü [[./a.org]]", "./a.org", "./a.org", 10, "")]
+    fn bracket_link(input: &str, target: &str, raw: &str, column: usize, text: &str) {
+        let le = OrgLinkExtractor();
+        let result = le.find_links(input);
+        let expected = MarkupLink {
+            text: Some(text.to_string()).filter(|t| !t.is_empty()),
+            ..link(target, 1, column)
+        };
+        assert_eq!(vec![expected.spanned_as(input, raw)], result);
+    }
+
+    #[test]
+    fn escaped_brackets() {
+        let le = OrgLinkExtractor();
+        let input = r"[[file:a\[1\].org][A]]";
+        let result = le.find_links(input);
+        let expected = MarkupLink {
+            text: Some("A".to_string()),
+            ..link("a[1].org", 1, 1)
+        };
+        assert_eq!(vec![expected.spanned_as(input, r"file:a\[1\].org")], result);
+    }
+
+    #[test_case("[[*Some Heading]]")]
+    #[test_case("[[Some text][Fuzzy]]")]
+    fn internal_link(input: &str) {
+        let le = OrgLinkExtractor();
+        let result = le.find_links(input);
+        assert!(result.is_empty());
+    }
+
+    #[test_case(
+        "Visit https://example.net/.",
+        "https://example.net/",
+        "https://example.net/",
+        7
+    )]
+    #[test_case(
+        "Visit <https://example.net/>",
+        "https://example.net/",
+        "https://example.net/",
+        8
+    )]
+    #[test_case(
+        "(see https://example.net/)",
+        "https://example.net/",
+        "https://example.net/",
+        6
+    )]
+    #[test_case("Open file:notes.org now", "notes.org", "file:notes.org", 6)]
+    #[test_case(
+        "Mail mailto:me@example.net",
+        "mailto:me@example.net",
+        "mailto:me@example.net",
+        6
+    )]
+    fn plain_link(input: &str, target: &str, raw: &str, column: usize) {
+        let le = OrgLinkExtractor();
+        let result = le.find_links(input);
+        let expected = MarkupLink {
+            kind: LinkKind::Autolink,
+            ..link(target, 1, column)
+        };
+        assert_eq!(vec![expected.spanned_as(input, raw)], result);
+    }
+
+    #[test_case("#+INCLUDE: \"chapter.org\"", "chapter.org", "chapter.org")]
+    #[test_case("#+include: \"code.rs::10\" src rust", "code.rs#L10", "code.rs::10")]
+    #[test_case("#+INCLUDE: ./chapter.org", "./chapter.org", "./chapter.org")]
+    fn include(input: &str, target: &str, raw: &str) {
+        let le = OrgLinkExtractor();
+        let result = le.find_links(input);
+        assert_eq!(vec![link(target, 1, 1).spanned_as(input, raw)], result);
+    }
+
+    #[test]
+    fn verbatim() {
+        let le = OrgLinkExtractor();
+        let input = "#+BEGIN_SRC org\n[[./a.org]]\n#+END_SRC\n# [[./b.org]]\n: [[./c.org]]\n#+begin_example\nhttps://example.net\n#+end_example\n[[./d.org]]";
+        let result = le.find_links(input);
+        assert_eq!(vec![link("./d.org", 9, 1).spanned(input)], result);
+    }
+
+    #[test_case("[[ file:notes.org::42 ][Notes]]", "file:notes.org::42")]
+    #[test_case("#+INCLUDE: \"chapter.org\"", "chapter.org")]
+    #[test_case("Ü (see https://example.net/)", "https://example.net/")]
+    fn span(input: &str, raw: &str) {
+        let le = OrgLinkExtractor();
+        let result = le.find_links(input);
+        assert_eq!(vec![raw], span_texts(input, &result));
+    }
+}
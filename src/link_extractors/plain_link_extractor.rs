@@ -0,0 +1,330 @@
+use crate::link_extractors::link_extractor::line_column_from_idx;
+use crate::link_extractors::link_extractor::trim_url;
+use crate::link_extractors::link_extractor::LinkExtractor;
+use crate::link_extractors::link_extractor::LinkKind;
+use crate::link_extractors::link_extractor::MarkupLink;
+use crate::link_extractors::link_extractor::Span;
+use crate::Config;
+use regex::Regex;
+use serde::de::Error;
+use serde::Deserialize;
+use serde::Deserializer;
+use std::collections::HashMap;
+use std::path::Path;
+use wildmatch::WildMatch;
+
+lazy_static! {
+    static ref URL_REGEX: Regex =
+        Regex::new(r#"\b(?:https?|ftps?)://[^\s<>"'`{}|\\^\[\]]+"#).unwrap();
+}
+
+/// Extracts the links of files of any type, like `.txt`, `.yml` or `Dockerfile`,
+/// with regular expressions
+#[derive(Default)]
+pub struct PlainLinkExtractor {
+    /// Find bare URLs like `https://example.net`
+    pub urls: bool,
+    /// Expressions capturing link targets in a group named `link`, or in their first group
+    pub patterns: Vec<Regex>,
+}
+
+impl PlainLinkExtractor {
+    /// The extractor of a file matching the globs of the `plain-files` option.
+    /// Globs without expressions find bare URLs.
+    #[must_use]
+    pub fn for_file(path: &str, config: &Config) -> Self {
+        let mut extractor = Self::default();
+        for patterns in plain_file_patterns(path, config).unwrap_or_default() {
+            if patterns.is_empty() {
+                extractor.urls = true;
+            }
+            extractor.patterns.extend_from_slice(patterns);
+        }
+        extractor
+    }
+}
+
+/// Deserializes the `plain-files` option, compiling the expressions of each glob once.
+/// An invalid expression is an error of the config file.
+///
+/// # Errors
+///
+/// Returns an error if the option is malformed or an expression is no valid regex
+pub fn deserialize_plain_files<'de, D>(
+    deserializer: D,
+) -> Result<Option<HashMap<String, Vec<Regex>>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let Some(plain_files) = Option::<HashMap<String, Vec<String>>>::deserialize(deserializer)?
+    else {
+        return Ok(None);
+    };
+    plain_files
+        .into_iter()
+        .map(|(glob, patterns)| {
+            let regexes = patterns
+                .iter()
+                .map(|pattern| {
+                    Regex::new(pattern).map_err(|e| {
+                        D::Error::custom(format!(
+                            "Invalid regex '{pattern}' of the plain-files option. {e}"
+                        ))
+                    })
+                })
+                .collect::<Result<_, _>>()?;
+            Ok((glob, regexes))
+        })
+        .collect::<Result<_, _>>()
+        .map(Some)
+}
+
+/// The expressions of all `plain-files` globs matching the file at `path`,
+/// or `None` if the file is no plain file.
+/// Globs containing a `/` are matched against the path relative to the checked directory,
+/// others against the file name.
+pub fn plain_file_patterns<'a>(path: &str, config: &'a Config) -> Option<Vec<&'a [Regex]>> {
+    let path = Path::new(path);
+    let relative = path
+        .strip_prefix(&config.directory)
+        .ok()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/");
+    let file_name = path
+        .file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let patterns: Vec<&[Regex]> = config
+        .optional
+        .plain_files
+        .as_ref()?
+        .iter()
+        .filter(|(glob, _)| {
+            let glob = glob.strip_prefix("./").unwrap_or(glob);
+            if glob.contains('/') {
+                WildMatch::new(glob).matches(relative.trim_start_matches("./"))
+            } else {
+                WildMatch::new(glob).matches(&file_name)
+            }
+        })
+        .map(|(_, patterns)| patterns.as_slice())
+        .collect();
+    Some(patterns).filter(|p| !p.is_empty())
+}
+
+impl LinkExtractor for PlainLinkExtractor {
+    fn find_links(&self, text: &str) -> Vec<MarkupLink> {
+        let mut result: Vec<(usize, String, LinkKind)> = Vec::new();
+        if self.urls {
+            for url in URL_REGEX.find_iter(text) {
+                let target = trim_url(url.as_str()).to_string();
+                result.push((url.start(), target, LinkKind::Autolink));
+            }
+        }
+        for pattern in &self.patterns {
+            for caps in pattern.captures_iter(text) {
+                let Some(target) = caps.name("link").or_else(|| caps.get(1)) else {
+                    continue;
+                };
+                if !target.as_str().is_empty() {
+                    let start = target.start();
+                    result.push((start, target.as_str().to_string(), LinkKind::Inline));
+                }
+            }
+        }
+
+        result.sort_by_key(|(idx, ..)| *idx);
+        result.dedup_by(|a, b| a.0 == b.0 && a.1 == b.1);
+        result
+            .into_iter()
+            .map(|(idx, target, kind)| {
+                let (line, column) = line_column_from_idx(text, idx);
+                let span = Span::new(text, idx..idx + target.len());
+                MarkupLink {
+                    target,
+                    line,
+                    column,
+                    span,
+                    kind,
+                    ..MarkupLink::default()
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::link_extractors::link_extractor::span_texts;
+    use crate::OptionalConfig;
+    use ntest::test_case;
+
+    fn urls(text: &str) -> Vec<(usize, usize, String)> {
+        let le = PlainLinkExtractor {
+            urls: true,
+            patterns: vec![],
+        };
+        le.find_links(text)
+            .into_iter()
+            .map(|l| (l.line, l.column, l.target))
+            .collect()
+    }
+
+    fn config(globs: &[(&str, &[&str])]) -> Config {
+        Config {
+            directory: "docs".into(),
+            optional: OptionalConfig {
+                plain_files: Some(
+                    globs
+                        .iter()
+                        .map(|(glob, patterns)| {
+                            let patterns =
+                                patterns.iter().map(|p| Regex::new(p).unwrap()).collect();
+                            (glob.to_string(), patterns)
+                        })
+                        .collect(),
+                ),
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test_case("See https://example.net.", "https://example.net")]
+    #[test_case("url: \"https://example.net/a?b=c#d\"", "https://example.net/a?b=c#d")]
+    #[test_case("{\"url\":\"https://example.net/x\"}", "https://example.net/x")]
+    #[test_case("(https://example.net/wiki/A_(B))", "https://example.net/wiki/A_(B)")]
+    #[test_case("<https://example.net>", "https://example.net")]
+    #[test_case("FROM x # ftp://example.net/file,", "ftp://example.net/file")]
+    #[test_case("ADD https://example.net/ü.tar /opt", "https://example.net/ü.tar")]
+    fn url(text: &str, target: &str) {
+        assert_eq!(
+            vec![target.to_string()],
+            urls(text).into_iter().map(|l| l.2).collect::<Vec<_>>()
+        );
+    }
+
+    #[test_case("example.net")]
+    #[test_case("https:// example.net")]
+    #[test_case("xhttps//example.net")]
+    fn no_url(text: &str) {
+        assert!(urls(text).is_empty());
+    }
+
+    #[test]
+    fn url_position() {
+        let text = "name: ä\nlinks:\n  - https://example.net\n  - http://example.org";
+        assert_eq!(
+            vec![
+                (3, 5, "https://example.net".to_string()),
+                (4, 5, "http://example.org".to_string())
+            ],
+            urls(text)
+        );
+    }
+
+    #[test]
+    fn capture_groups() {
+        let le = PlainLinkExtractor {
+            urls: false,
+            patterns: vec![
+                Regex::new(r#"import "([^"]+)";"#).unwrap(),
+                Regex::new(r"(?m)^see: (?P<link>\S+)$").unwrap(),
+                Regex::new(r"no group").unwrap(),
+            ],
+        };
+        let text = "import \"a.proto\";\nsee: ./b.md\nno group https://example.net";
+        let result: Vec<(usize, usize, String)> = le
+            .find_links(text)
+            .into_iter()
+            .map(|l| (l.line, l.column, l.target))
+            .collect();
+        assert_eq!(
+            vec![(1, 9, "a.proto".to_string()), (2, 6, "./b.md".to_string())],
+            result
+        );
+    }
+
+    #[test]
+    fn kinds() {
+        let le = PlainLinkExtractor {
+            urls: true,
+            patterns: vec![Regex::new(r"see: (\S+)").unwrap()],
+        };
+        let kinds: Vec<LinkKind> = le
+            .find_links("https://example.net\nsee: ./b.md")
+            .into_iter()
+            .map(|l| l.kind)
+            .collect();
+        assert_eq!(vec![LinkKind::Autolink, LinkKind::Inline], kinds);
+    }
+
+    #[test_case("docs/notes.txt", true)]
+    #[test_case("docs/sub/notes.TXT", false)]
+    #[test_case("docs/sub/Dockerfile", true)]
+    #[test_case("docs/ci/build.yml", true)]
+    #[test_case("docs/build.yml", false)]
+    #[test_case("docs/proto/api.proto", true)]
+    fn plain_file(path: &str, plain: bool) {
+        let config = config(&[
+            ("*.txt", &[]),
+            ("Dockerfile", &[]),
+            ("ci/*.yml", &[]),
+            ("./proto/*", &["import \"([^\"]+)\""]),
+        ]);
+        assert_eq!(plain, plain_file_patterns(path, &config).is_some());
+    }
+
+    #[test]
+    fn for_file() {
+        let config = config(&[("*.yml", &[]), ("ci/*", &["image: (\\S+)"])]);
+        let le = PlainLinkExtractor::for_file("docs/ci/build.yml", &config);
+        assert!(le.urls);
+        assert_eq!(1, le.patterns.len());
+        let le = PlainLinkExtractor::for_file("docs/ci/build.sh", &config);
+        assert!(!le.urls);
+        assert_eq!(1, le.patterns.len());
+    }
+
+    #[test]
+    fn deserialize() {
+        let toml = "[plain-files]\n\"*.txt\" = []\n\"*.proto\" = ['import \"([^\"]+)\";']";
+        let config: OptionalConfig = toml::from_str(toml).unwrap();
+        let plain_files = config.plain_files.unwrap();
+        assert!(plain_files["*.txt"].is_empty());
+        assert_eq!(
+            vec![r#"import "([^"]+)";"#],
+            plain_files["*.proto"]
+                .iter()
+                .map(Regex::as_str)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn invalid_regex() {
+        let toml = "[plain-files]\n\"*.txt\" = ['(unclosed']";
+        let err = toml::from_str::<OptionalConfig>(toml).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Invalid regex '(unclosed' of the plain-files option."));
+    }
+
+    #[test]
+    fn spans() {
+        let le = PlainLinkExtractor {
+            urls: true,
+            patterns: vec![Regex::new(r"see: (\S+)").unwrap()],
+        };
+        let input = "ü https://example.net.\nsee: ./b.md";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec!["https://example.net", "./b.md"],
+            span_texts(input, &result)
+        );
+        assert_eq!(3, result[0].span.start.char_column);
+        assert_eq!(4, result[0].span.start.byte_column);
+    }
+}
@@ -0,0 +1,479 @@
+use crate::link_extractors::link_extractor::line_column_from_idx;
+use crate::link_extractors::link_extractor::trimmed_range;
+use crate::link_extractors::link_extractor::LinkExtractor;
+use crate::link_extractors::link_extractor::LinkIssue;
+use crate::link_extractors::link_extractor::LinkKind;
+use crate::link_extractors::link_extractor::MarkupLink;
+use crate::link_extractors::link_extractor::Span;
+use regex::Regex;
+use std::collections::HashSet;
+
+pub struct RstLinkExtractor();
+
+lazy_static! {
+    // `.. image:: path`, also as part of a substitution definition
+    static ref DIRECTIVE_REGEX: Regex =
+        Regex::new(r"^(\s*)\.\.\s+(?:\|[^|]+\|\s+)?(image|figure|include)::[ \t]*(\S.*?)\s*$")
+            .unwrap();
+    // `.. _name: url`, `.. __: url` and `__ url`
+    static ref TARGET_REGEX: Regex =
+        Regex::new(r"^(\s*)(?:\.\.\s+(?:_[^:`]+|_`[^`]+`|__):|__)[ \t]+(\S+)\s*$").unwrap();
+    // `.. _label:` in front of a section or element, which `:ref:` roles refer to
+    static ref LABEL_REGEX: Regex =
+        Regex::new(r"^\s*\.\.\s+_(?:`([^`]+)`|([^:`]+)):\s*$").unwrap();
+    // `:target: url` option of the image and figure directives
+    static ref TARGET_OPTION_REGEX: Regex = Regex::new(r"^(\s+):target:[ \t]+(\S+)\s*$").unwrap();
+    static ref LITERAL_DIRECTIVE_REGEX: Regex =
+        Regex::new(r"^\s*\.\.\s+(?:code|code-block|sourcecode|parsed-literal|math|raw)::").unwrap();
+    static ref DIRECTIVE_START_REGEX: Regex = Regex::new(r"^\s*\.\.\s+[\w:+.-]+::").unwrap();
+    static ref COMMENT_REGEX: Regex = Regex::new(r"^\s*\.\.(?:\s*$|\s+[^\[|_\s])").unwrap();
+    static ref INLINE_LITERAL_REGEX: Regex = Regex::new(r"(?s)``.+?``").unwrap();
+    static ref INTERPRETED_TEXT_REGEX: Regex =
+        Regex::new(r"(?s)(:[a-zA-Z][\w+.:-]*:)?`([^`]+)`(__?)?").unwrap();
+    static ref EMBEDDED_TARGET_REGEX: Regex = Regex::new(r"(?s)<([^<>]+)>\s*$").unwrap();
+}
+
+impl LinkExtractor for RstLinkExtractor {
+    fn find_links(&self, text: &str) -> Vec<MarkupLink> {
+        let Blocks {
+            links: mut result,
+            inline_text,
+            labels,
+        } = find_block_links(text);
+        let inline_text = INLINE_LITERAL_REGEX
+            .replace_all(&inline_text, |caps: &regex::Captures| {
+                " ".repeat(caps[0].len())
+            });
+        for caps in INTERPRETED_TEXT_REGEX.captures_iter(&inline_text) {
+            let content = &caps[2];
+            let content_start = caps.get(2).unwrap().start();
+            let embedded_range = EMBEDDED_TARGET_REGEX
+                .captures(content)
+                .and_then(|target| target.get(1))
+                .map(|target| target.range());
+            let embedded_target = embedded_range
+                .clone()
+                .map(|range| content[range].split_whitespace().collect::<String>());
+            let has_embedded_target = embedded_target.is_some();
+            let mut issue = None;
+            let target = match (caps.get(1).map(|role| role.as_str()), caps.get(3)) {
+                (Some(":doc:"), _) => embedded_target.unwrap_or_else(|| content.trim().to_string()),
+                // Labels of the document are fine, there is no target left to check
+                (Some(":ref:"), _) => {
+                    let label = normalize_label(
+                        embedded_range
+                            .clone()
+                            .map_or(content, |range| &content[range]),
+                    );
+                    if labels.contains(&label) {
+                        continue;
+                    }
+                    issue = Some(LinkIssue::UndefinedLabel(label.clone()));
+                    label
+                }
+                // Embedded URI, unless it is an alias referring to another target
+                (None, Some(_)) => match embedded_target {
+                    Some(target) if !target.ends_with('_') => target,
+                    Some(_) | None => continue,
+                },
+                (Some(_) | None, _) => continue,
+            };
+            let (line, column) = line_column_from_idx(text, caps.get(0).unwrap().start());
+            // The text in front of an embedded target like `Title <target>`
+            let link_text = content
+                .rfind('<')
+                .filter(|_| has_embedded_target)
+                .map(|end| {
+                    content[..end]
+                        .split_whitespace()
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .filter(|text| !text.is_empty());
+            let range = embedded_range.unwrap_or(0..content.len());
+            let range = trimmed_range(text, content_start + range.start..content_start + range.end);
+            result.push(MarkupLink {
+                text: link_text,
+                issue,
+                ..new_link(
+                    line,
+                    column,
+                    Span::new(text, range),
+                    &target,
+                    LinkKind::Inline,
+                )
+            });
+        }
+        result
+    }
+}
+
+fn new_link(line: usize, column: usize, span: Span, target: &str, kind: LinkKind) -> MarkupLink {
+    MarkupLink {
+        line,
+        column,
+        span,
+        target: target.to_string(),
+        kind,
+        ..MarkupLink::default()
+    }
+}
+
+/// Labels are case-insensitive and ignore differences of white-space
+fn normalize_label(label: &str) -> String {
+    label
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// What is found in the blocks of a document, before looking at its inline markup
+struct Blocks {
+    /// Links of directives and hyperlink targets
+    links: Vec<MarkupLink>,
+    /// The text with the lines of these links, comments and literal blocks replaced by
+    /// white-space, keeping the byte offsets of the remaining inline markup intact
+    inline_text: String,
+    /// The normalized labels of internal targets like `.. _label:`
+    labels: HashSet<String>,
+}
+
+/// Finds the links of directives and hyperlink targets, and the labels of internal targets.
+fn find_block_links(text: &str) -> Blocks {
+    let mut result: Vec<MarkupLink> = Vec::new();
+    let mut labels = HashSet::new();
+    let mut inline_text = String::with_capacity(text.len());
+    // Lines indented deeper than this belong to a comment or literal block
+    let mut masked_indent: Option<usize> = None;
+    // A literal block is expected after a paragraph ending with `::`
+    let mut literal_indent: Option<usize> = None;
+    let mut line_start = 0;
+    for (line_idx, line) in text.split('\n').enumerate() {
+        let span_of =
+            |m: regex::Match| Span::new(text, line_start + m.start()..line_start + m.end());
+        // Links of blocks are reported at their target, not at the leading `..`
+        let column_of = |m: regex::Match| line[..m.start()].chars().count() + 1;
+        if line_idx > 0 {
+            inline_text.push('\n');
+        }
+        let indent = line.chars().take_while(|c| c.is_whitespace()).count();
+        let blank = line.trim().is_empty();
+        let mut masked = true;
+
+        if masked_indent.is_some_and(|parent| blank || indent > parent) {
+            // Still inside a comment or literal block
+        } else if literal_indent.is_some() && blank {
+            masked = false;
+        } else if let Some(parent) = literal_indent.take().filter(|parent| indent > *parent) {
+            masked_indent = Some(parent);
+        } else {
+            masked_indent = None;
+            if let Some(caps) = DIRECTIVE_REGEX.captures(line) {
+                if !caps[3].starts_with('<') {
+                    let kind = if &caps[2] == "include" {
+                        LinkKind::Inline
+                    } else {
+                        LinkKind::Image
+                    };
+                    let target = caps.get(3).unwrap();
+                    let (column, span) = (column_of(target), span_of(target));
+                    result.push(new_link(line_idx + 1, column, span, &caps[3], kind));
+                }
+            } else if let Some(caps) = LABEL_REGEX.captures(line) {
+                let label = caps.get(1).or_else(|| caps.get(2)).unwrap().as_str();
+                labels.insert(normalize_label(label));
+            } else if let Some(caps) = TARGET_REGEX.captures(line) {
+                if !caps[2].ends_with('_') {
+                    let kind = LinkKind::Reference;
+                    let target = caps.get(2).unwrap();
+                    let (column, span) = (column_of(target), span_of(target));
+                    result.push(new_link(line_idx + 1, column, span, &caps[2], kind));
+                }
+            } else if let Some(caps) = TARGET_OPTION_REGEX.captures(line) {
+                let kind = LinkKind::Inline;
+                let target = caps.get(2).unwrap();
+                let (column, span) = (column_of(target), span_of(target));
+                result.push(new_link(line_idx + 1, column, span, &caps[2], kind));
+            } else if LITERAL_DIRECTIVE_REGEX.is_match(line)
+                || (COMMENT_REGEX.is_match(line) && !DIRECTIVE_START_REGEX.is_match(line))
+            {
+                masked_indent = Some(indent);
+            } else {
+                masked = false;
+                if line.trim_end().ends_with("::") && !DIRECTIVE_START_REGEX.is_match(line) {
+                    literal_indent = Some(indent);
+                }
+            }
+        }
+
+        if masked {
+            inline_text.push_str(&" ".repeat(line.len()));
+        } else {
+            inline_text.push_str(line);
+        }
+        line_start += line.len() + 1;
+    }
+    Blocks {
+        links: result,
+        inline_text,
+        labels,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::link_extractors::link_extractor::link;
+    use crate::link_extractors::link_extractor::span_texts;
+    use ntest::test_case;
+
+    fn text_link(text: &str, target: &str, line: usize, column: usize) -> MarkupLink {
+        MarkupLink {
+            text: Some(text.to_string()),
+            ..link(target, line, column)
+        }
+    }
+
+    #[test]
+    fn no_link() {
+        let le = RstLinkExtractor();
+        let input =
+            "Title\n=====\n\nThis is *no* link, just `interpreted text` and a `name`_ reference.";
+        let result = le.find_links(input);
+        assert!(result.is_empty());
+    }
+
+    #[test_case(
+        "`Example <http://example.net/>`_",
+        "http://example.net/",
+        1,
+        "Example"
+    )]
+    #[test_case(
+        "`Example <http://example.net/>`__",
+        "http://example.net/",
+        1,
+        "Example"
+    )]
+    #[test_case(
+        "See the `Example\n  Page <http://example.net/>`_ for more.",
+        "http://example.net/",
+        9,
+        "Example Page"
+    )]
+    #[test_case(
+        "Long `Example <http://\n   example.net/>`_",
+        "http://\n   example.net/",
+        6,
+        "Example"
+    )]
+    #[test_case("Umlaut `Ü <http://example.net/>`_", "http://example.net/", 8, "Ü")]
+    fn inline_link(input: &str, raw: &str, column: usize, text: &str) {
+        let le = RstLinkExtractor();
+        let result = le.find_links(input);
+        let expected = text_link(text, "http://example.net/", 1, column);
+        assert_eq!(vec![expected.spanned_as(input, raw)], result);
+    }
+
+    #[test]
+    fn alias_is_no_link() {
+        let le = RstLinkExtractor();
+        let result = le.find_links("`Example <example_>`_");
+        assert!(result.is_empty());
+    }
+
+    #[test_case(".. _Example: http://example.net/", 14)]
+    #[test_case(".. _`Example: the page`: http://example.net/", 26)]
+    #[test_case(".. __: http://example.net/", 8)]
+    #[test_case("__ http://example.net/", 4)]
+    #[test_case("   .. _example: http://example.net/", 17)]
+    #[test_case(".. _Über: http://example.net/", 11)]
+    fn named_target(input: &str, column: usize) {
+        let le = RstLinkExtractor();
+        let result = le.find_links(input);
+        let expected = MarkupLink {
+            kind: LinkKind::Reference,
+            ..link("http://example.net/", 1, column)
+        };
+        assert_eq!(vec![expected.spanned(input)], result);
+    }
+
+    #[test_case(".. _internal-label:")]
+    #[test_case(".. _indirect: other_")]
+    fn not_a_target(input: &str) {
+        let le = RstLinkExtractor();
+        let result = le.find_links(input);
+        assert!(result.is_empty());
+    }
+
+    #[test_case("See :doc:`usage`.", "usage", "")]
+    #[test_case("See :doc:`the usage <../usage>`.", "../usage", "the usage")]
+    fn role(input: &str, target: &str, text: &str) {
+        let le = RstLinkExtractor();
+        let result = le.find_links(input);
+        let expected = MarkupLink {
+            text: Some(text.to_string()).filter(|t| !t.is_empty()),
+            ..link(target, 1, 5)
+        };
+        assert_eq!(vec![expected.spanned(input)], result);
+    }
+
+    #[test_case("See :ref:`install-label`.\n\n.. _install-label:\n\nInstall\n=======")]
+    #[test_case("See :ref:`Installing <install label>`.\n\n.. _`Install  Label`:")]
+    fn defined_label(input: &str) {
+        let le = RstLinkExtractor();
+        let result = le.find_links(input);
+        assert!(result.is_empty());
+    }
+
+    #[test_case("See :ref:`install-label`.", "install-label", "")]
+    #[test_case(
+        "See :ref:`Installing <install-label>`.\n\n.. _other-label:",
+        "install-label",
+        "Installing"
+    )]
+    fn undefined_label(input: &str, label: &str, text: &str) {
+        let le = RstLinkExtractor();
+        let result = le.find_links(input);
+        let expected = MarkupLink {
+            text: Some(text.to_string()).filter(|t| !t.is_empty()),
+            issue: Some(LinkIssue::UndefinedLabel(label.to_string())),
+            ..link(label, 1, 5)
+        };
+        assert_eq!(vec![expected.spanned(input)], result);
+    }
+
+    #[test]
+    fn other_roles() {
+        let le = RstLinkExtractor();
+        let result = le.find_links("The :class:`Foo <foo.Foo>` and :py:func:`bar` APIs.");
+        assert!(result.is_empty());
+    }
+
+    #[test_case(".. image:: images/logo.png", "images/logo.png", 12, true)]
+    #[test_case(".. figure:: ./figure.svg", "./figure.svg", 13, true)]
+    #[test_case(".. include:: ../README.rst", "../README.rst", 14, false)]
+    #[test_case(".. |logo| image:: logo.png", "logo.png", 19, true)]
+    #[test_case("  ..  image::   logo.png", "logo.png", 17, true)]
+    fn directive(input: &str, target: &str, column: usize, image: bool) {
+        let le = RstLinkExtractor();
+        let result = le.find_links(input);
+        let kind = if image {
+            LinkKind::Image
+        } else {
+            LinkKind::Inline
+        };
+        assert_eq!(
+            vec![MarkupLink {
+                kind,
+                ..link(target, 1, column)
+            }
+            .spanned(input)],
+            result
+        );
+    }
+
+    #[test]
+    fn image_target_option() {
+        let le = RstLinkExtractor();
+        let input = ".. image:: logo.png\n   :alt: Logo\n   :target: https://example.net/\n";
+        let result = le.find_links(input);
+        let image = MarkupLink {
+            kind: LinkKind::Image,
+            ..link("logo.png", 1, 12)
+        };
+        assert_eq!(
+            vec![
+                image.spanned(input),
+                link("https://example.net/", 3, 13).spanned(input)
+            ],
+            result
+        );
+    }
+
+    #[test]
+    fn standard_include() {
+        let le = RstLinkExtractor();
+        let result = le.find_links(".. include:: <isonum.txt>");
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn inline_literal() {
+        let le = RstLinkExtractor();
+        let result = le.find_links("No link: ``Example <http://example.net/>`_``.");
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn comment() {
+        let le = RstLinkExtractor();
+        let input = ".. This is a comment\n   with `Example <http://example.net/>`_\n\n`Link <http://example.net/>`_";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![text_link("Link", "http://example.net/", 4, 1).spanned(input)],
+            result
+        );
+    }
+
+    #[test]
+    fn literal_block() {
+        let le = RstLinkExtractor();
+        let input = "Example::\n\n   `Example <http://example.net/>`_\n   .. image:: no.png\n\n`Link <http://example.net/>`_";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![text_link("Link", "http://example.net/", 6, 1).spanned(input)],
+            result
+        );
+    }
+
+    #[test]
+    fn code_block() {
+        let le = RstLinkExtractor();
+        let input = ".. code-block:: rst\n\n   `Example <http://example.net/>`_\n\n`Link <http://example.net/>`_";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![text_link("Link", "http://example.net/", 5, 1).spanned(input)],
+            result
+        );
+    }
+
+    #[test]
+    fn directive_content() {
+        let le = RstLinkExtractor();
+        let input = ".. note::\n\n   See `Example <http://example.net/>`_";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec![text_link("Example", "http://example.net/", 3, 8).spanned(input)],
+            result
+        );
+    }
+
+    #[test_case(".. image:: images/logo.png", "images/logo.png")]
+    #[test_case(".. _Example: http://example.net/", "http://example.net/")]
+    #[test_case("See `Example <http://example.net/>`_.", "http://example.net/")]
+    #[test_case("`Long <http://\n   example.net/>`_", "http://\n   example.net/")]
+    #[test_case("See :doc:`usage`.", "usage")]
+    #[test_case("See :doc:`the usage <../usage>`.", "../usage")]
+    fn span(input: &str, raw: &str) {
+        let le = RstLinkExtractor();
+        let result = le.find_links(input);
+        assert_eq!(vec![raw], span_texts(input, &result));
+    }
+
+    #[test]
+    fn span_of_target_option() {
+        let le = RstLinkExtractor();
+        let input = "Ü\n\n.. image:: logo.png\n   :target: https://example.net/\n";
+        let result = le.find_links(input);
+        assert_eq!(
+            vec!["logo.png", "https://example.net/"],
+            span_texts(input, &result)
+        );
+        assert_eq!(
+            (4, 13),
+            (result[1].span.start.line, result[1].span.start.char_column)
+        );
+    }
+}
@@ -0,0 +1,625 @@
+use crate::link_extractors::link_extractor::LinkExtractor;
+use crate::link_extractors::link_extractor::MarkupLink;
+use crate::link_extractors::link_extractor::Span;
+use crate::link_extractors::markdown_link_extractor::MarkdownLinkExtractor;
+use crate::Config;
+use regex::Regex;
+
+/// Extracts the links of the markdown written in the comments of source code files
+pub struct SourceCodeLinkExtractor {
+    pub syntax: CommentSyntax,
+}
+
+/// The kind of comments which are checked for links
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommentSyntax {
+    /// Rust doc comments like `///`, `//!`, `/** */` and `#[doc = "..."]` attributes
+    RustDoc,
+    /// Comments starting with one of the given markers, like `#` or `//`.
+    ///
+    /// Block comments are given as start and end marker separated by a space, like `/* */`.
+    Markers(Vec<String>),
+}
+
+impl CommentSyntax {
+    /// The comment syntax of a source file with the given `path`.
+    /// Configured file extensions take precedence over the Rust doc comments of `.rs` files.
+    #[must_use]
+    pub fn for_file(path: &str, config: &Config) -> Self {
+        let extension = std::path::Path::new(path)
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase());
+        config
+            .optional
+            .comment_syntax
+            .as_ref()
+            .and_then(|c| {
+                c.iter()
+                    .find(|(ext, _)| Some(ext.to_lowercase()) == extension)
+                    .map(|(_, markers)| Self::Markers(markers.clone()))
+            })
+            .unwrap_or(Self::RustDoc)
+    }
+}
+
+/// The comment text of a single source line
+#[derive(Default)]
+struct CommentLine {
+    text: String,
+    /// Byte offsets where parts of the comment start, in `text` and in the source line
+    segments: Vec<(usize, usize)>,
+}
+
+/// The comments of a source file. Each source line has exactly one comment line,
+/// which is empty if the source line has no comment.
+struct CommentText<'a> {
+    source: &'a str,
+    line_starts: Vec<usize>,
+    lines: Vec<CommentLine>,
+}
+
+impl<'a> CommentText<'a> {
+    fn new(source: &'a str) -> Self {
+        let line_starts: Vec<usize> = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(idx, _)| idx + 1))
+            .collect();
+        let lines = line_starts.iter().map(|_| CommentLine::default()).collect();
+        CommentText {
+            source,
+            line_starts,
+            lines,
+        }
+    }
+
+    fn line_idx(&self, idx: usize) -> usize {
+        self.line_starts.partition_point(|start| *start <= idx) - 1
+    }
+
+    /// Adds `text` found at byte index `idx` of the source
+    fn push_text(&mut self, idx: usize, text: &str) {
+        let line_idx = self.line_idx(idx);
+        let column = idx - self.line_starts[line_idx];
+        let line = &mut self.lines[line_idx];
+        line.segments.push((line.text.len(), column));
+        line.text.push_str(text);
+    }
+
+    /// Separates a new comment from a previous one on the same line
+    fn begin_comment(&mut self, idx: usize) {
+        let line_idx = self.line_idx(idx);
+        let line = &mut self.lines[line_idx];
+        if !line.text.is_empty() {
+            line.text.push(' ');
+        }
+    }
+
+    /// Adds the comment content in between the byte indices `start` and `end` of the source.
+    /// The decoration of block comment lines, like ` * `, is skipped.
+    fn push_lines(&mut self, start: usize, end: usize, block: bool) {
+        let source = self.source;
+        let mut part_start = start;
+        for (n, part) in source[start..end].split('\n').enumerate() {
+            let trimmed = part.trim_end_matches('\r');
+            let mut content = trimmed;
+            if n > 0 && block {
+                content = content.trim_start();
+                content = content.strip_prefix('*').unwrap_or(content);
+                content = content.strip_prefix(' ').unwrap_or(content);
+            }
+            if !content.is_empty() {
+                self.push_text(part_start + trimmed.len() - content.len(), content);
+            }
+            part_start += part.len() + 1;
+        }
+    }
+
+    /// Adds a comment, skipping the space in between the comment marker and the content
+    fn push_comment(&mut self, start: usize, end: usize, block: bool) {
+        self.begin_comment(start);
+        let start = skip_space(self.source, start).min(end);
+        self.push_lines(start, end, block);
+    }
+
+    fn text(&self) -> String {
+        self.lines
+            .iter()
+            .map(|line| line.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Maps a line and character column of the comment text to a column of the source line
+    fn source_column(&self, line: usize, column: usize) -> usize {
+        let Some(comment_line) = self.lines.get(line - 1) else {
+            return column;
+        };
+        let byte_column = comment_line
+            .text
+            .char_indices()
+            .nth(column - 1)
+            .map_or(comment_line.text.len(), |(idx, _)| idx)
+            + 1;
+        let line_start = self.line_starts[line - 1];
+        self.source[line_start..self.source_offset(line, byte_column)]
+            .chars()
+            .count()
+            + 1
+    }
+
+    /// Maps a line and byte column of the comment text to a byte index of the source
+    fn source_offset(&self, line: usize, byte_column: usize) -> usize {
+        let Some(line_start) = self.line_starts.get(line - 1) else {
+            return self.source.len();
+        };
+        let line_end = self
+            .line_starts
+            .get(line)
+            .map_or(self.source.len(), |next| next - 1);
+        let offset = byte_column - 1;
+        let column = self
+            .lines
+            .get(line - 1)
+            .and_then(|l| l.segments.iter().rev().find(|(start, _)| *start <= offset))
+            .map_or(offset, |(text_start, source_start)| {
+                source_start + offset - text_start
+            });
+        let mut idx = (line_start + column).min(line_end);
+        while !self.source.is_char_boundary(idx) {
+            idx -= 1;
+        }
+        idx
+    }
+}
+
+impl LinkExtractor for SourceCodeLinkExtractor {
+    fn find_links(&self, text: &str) -> Vec<MarkupLink> {
+        let mut comments = CommentText::new(text);
+        match &self.syntax {
+            CommentSyntax::RustDoc => collect_rust_doc(&mut comments),
+            CommentSyntax::Markers(markers) => collect_comments(&mut comments, markers),
+        }
+        MarkdownLinkExtractor::default()
+            .find_links(&comments.text())
+            .into_iter()
+            .filter(|link| {
+                self.syntax != CommentSyntax::RustDoc || !is_intra_doc_link(&link.target)
+            })
+            .map(|link| {
+                let (start, end) = (link.span.start, link.span.end);
+                let range = comments.source_offset(start.line, start.byte_column)
+                    ..comments.source_offset(end.line, end.byte_column);
+                MarkupLink {
+                    column: comments.source_column(link.line, link.column),
+                    span: Span::new(text, range),
+                    ..link
+                }
+            })
+            .collect()
+    }
+}
+
+/// Rustdoc resolves links to item paths like `crate::Config` or `Vec::new()`.
+/// Bare words like `guide` may be relative files and are still checked.
+fn is_intra_doc_link(target: &str) -> bool {
+    lazy_static! {
+        static ref ITEM_PATH_REGEX: Regex = Regex::new(
+            r"^([a-z]+@)?([A-Za-z_][A-Za-z0-9_]*)((?:::[A-Za-z_][A-Za-z0-9_]*)*)(!|\(\))?$"
+        )
+        .unwrap();
+    }
+    ITEM_PATH_REGEX.captures(target).is_some_and(|captures| {
+        captures.get(1).is_some()
+            || !captures[3].is_empty()
+            || captures.get(4).is_some()
+            || ["crate", "self", "super", "std", "core", "alloc"].contains(&&captures[2])
+    })
+}
+
+/// Collects the doc comments and `#[doc]` attributes of Rust code
+fn collect_rust_doc(comments: &mut CommentText) {
+    lazy_static! {
+        static ref DOC_ATTRIBUTE_REGEX: Regex =
+            Regex::new(r#"^#!?\[\s*doc\s*=\s*(r#*)?""#).unwrap();
+    }
+    let source = comments.source;
+    let mut idx = 0;
+    while idx < source.len() {
+        let rest = &source[idx..];
+        if rest.starts_with("//") {
+            let end = rest.find('\n').map_or(source.len(), |end| idx + end);
+            if (rest.starts_with("///") && !rest.starts_with("////")) || rest.starts_with("//!") {
+                comments.push_comment(idx + 3, end, false);
+            }
+            idx = end;
+        } else if rest.starts_with("/*") {
+            let end = block_comment_end(rest).map_or(source.len(), |end| idx + end);
+            let is_doc =
+                (rest.starts_with("/**") && !rest.starts_with("/***")) || rest.starts_with("/*!");
+            if is_doc && end - idx > 4 {
+                comments.push_comment(idx + 3, end - 2, true);
+            }
+            idx = end;
+        } else if let Some(caps) = DOC_ATTRIBUTE_REGEX.captures(rest) {
+            let content_start = idx + caps[0].len();
+            idx = if let Some(raw) = caps.get(1) {
+                let terminator = format!("\"{}", &raw.as_str()[1..]);
+                let end = source[content_start..]
+                    .find(&terminator)
+                    .map_or(source.len(), |end| content_start + end);
+                comments.push_comment(content_start, end, false);
+                (end + terminator.len()).min(source.len())
+            } else {
+                comments.begin_comment(content_start);
+                push_string_literal(comments, skip_space(source, content_start))
+            };
+        } else if let Some(end) = skip_literal(rest) {
+            idx += end;
+        } else {
+            idx += rest.chars().next().map_or(1, char::len_utf8);
+        }
+    }
+}
+
+fn skip_space(text: &str, idx: usize) -> usize {
+    if text[idx..].starts_with(' ') {
+        idx + 1
+    } else {
+        idx
+    }
+}
+
+/// The byte index behind the end of the (nested) block comment at the start of `text`
+fn block_comment_end(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut idx = 0;
+    while idx < text.len() {
+        if text[idx..].starts_with("/*") {
+            depth += 1;
+            idx += 2;
+        } else if text[idx..].starts_with("*/") {
+            depth -= 1;
+            idx += 2;
+            if depth == 0 {
+                return Some(idx);
+            }
+        } else {
+            idx += text[idx..].chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    None
+}
+
+/// Adds the content of the string literal starting at byte index `start`,
+/// and returns the byte index behind the closing quote.
+fn push_string_literal(comments: &mut CommentText, start: usize) -> usize {
+    let source = comments.source;
+    let mut chunk_start = start;
+    let mut chars = source[start..].char_indices();
+    while let Some((offset, c)) = chars.next() {
+        let idx = start + offset;
+        match c {
+            '"' => {
+                comments.push_lines(chunk_start, idx, false);
+                return idx + 1;
+            }
+            '\\' => {
+                comments.push_lines(chunk_start, idx, false);
+                let escaped = chars.next();
+                let unescaped = match escaped {
+                    Some((_, 't')) => "\t",
+                    Some((_, '"')) => "\"",
+                    Some((_, '\'')) => "'",
+                    Some((_, '\\')) => "\\",
+                    _ => "",
+                };
+                if !unescaped.is_empty() {
+                    comments.push_text(idx, unescaped);
+                }
+                chunk_start = escaped.map_or(source.len(), |(o, e)| start + o + e.len_utf8());
+            }
+            _ => (),
+        }
+    }
+    comments.push_lines(chunk_start, source.len(), false);
+    source.len()
+}
+
+/// The length of the string or character literal at the start of `text`, if there is one.
+/// Lifetimes like `'a` are no literals.
+fn skip_literal(text: &str) -> Option<usize> {
+    lazy_static! {
+        static ref RAW_STRING_REGEX: Regex = Regex::new(r#"^b?r(#*)""#).unwrap();
+        static ref CHAR_REGEX: Regex =
+            Regex::new(r"^b?'(?:\\(?:u\{[0-9a-fA-F]+\}|x[0-9a-fA-F]{2}|.)|[^\\'])'").unwrap();
+    }
+    if let Some(caps) = RAW_STRING_REGEX.captures(text) {
+        let start = caps[0].len();
+        let terminator = format!("\"{}", &caps[1]);
+        return Some(
+            text[start..]
+                .find(&terminator)
+                .map_or(text.len(), |end| start + end + terminator.len()),
+        );
+    }
+    if let Some(m) = CHAR_REGEX.find(text) {
+        return Some(m.end());
+    }
+    let quoted = text
+        .strip_prefix('"')
+        .or_else(|| text.strip_prefix("b\""))?;
+    let start = text.len() - quoted.len();
+    let mut chars = quoted.char_indices();
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '"' => return Some(start + idx + 1),
+            '\\' => {
+                chars.next();
+            }
+            _ => (),
+        }
+    }
+    Some(text.len())
+}
+
+/// Collects the comments written with the configured `markers`,
+/// skipping quoted strings of the code.
+fn collect_comments(comments: &mut CommentText, markers: &[String]) {
+    let mut line_markers: Vec<&str> = Vec::new();
+    let mut block_markers: Vec<(&str, &str)> = Vec::new();
+    for marker in markers {
+        match marker.split_once(char::is_whitespace) {
+            Some((start, end)) => block_markers.push((start.trim(), end.trim())),
+            None if !marker.is_empty() => line_markers.push(marker),
+            None => (),
+        }
+    }
+    // Longer markers first, so that `///` is no `//` comment
+    line_markers.sort_by_key(|m| std::cmp::Reverse(m.len()));
+    block_markers.sort_by_key(|(start, _)| std::cmp::Reverse(start.len()));
+
+    let source = comments.source;
+    let mut idx = 0;
+    while idx < source.len() {
+        let rest = &source[idx..];
+        if let Some((start, end)) = block_markers
+            .iter()
+            .find(|(start, _)| rest.starts_with(start))
+        {
+            let content_start = idx + start.len();
+            let content_end = source[content_start..]
+                .find(end)
+                .map_or(source.len(), |e| content_start + e);
+            comments.push_comment(content_start, content_end, true);
+            idx = (content_end + end.len()).min(source.len());
+        } else if let Some(marker) = line_markers.iter().find(|m| rest.starts_with(*m)) {
+            let end = rest.find('\n').map_or(source.len(), |end| idx + end);
+            comments.push_comment(idx + marker.len(), end, false);
+            idx = end;
+        } else if rest.starts_with(['"', '\'']) {
+            let quote = rest.chars().next().unwrap();
+            let mut end = source.len();
+            let mut chars = rest.char_indices().skip(1);
+            while let Some((offset, c)) = chars.next() {
+                if c == '\\' {
+                    chars.next();
+                } else if c == quote || c == '\n' {
+                    end = idx + offset + 1;
+                    break;
+                }
+            }
+            idx = end;
+        } else {
+            idx += rest.chars().next().map_or(1, char::len_utf8);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::link_extractors::link_extractor::link;
+    use crate::link_extractors::link_extractor::span_texts;
+    use crate::link_extractors::link_extractor::LinkKind;
+    use ntest::test_case;
+
+    fn text_link(text: &str, target: &str, line: usize, column: usize) -> MarkupLink {
+        MarkupLink {
+            text: Some(text.to_string()),
+            ..link(target, line, column)
+        }
+    }
+
+    fn rust_links(input: &str) -> Vec<MarkupLink> {
+        let extractor = SourceCodeLinkExtractor {
+            syntax: CommentSyntax::RustDoc,
+        };
+        extractor.find_links(input)
+    }
+
+    fn marker_links(markers: &[&str], input: &str) -> Vec<MarkupLink> {
+        let extractor = SourceCodeLinkExtractor {
+            syntax: CommentSyntax::Markers(markers.iter().map(ToString::to_string).collect()),
+        };
+        extractor.find_links(input)
+    }
+
+    #[test_case("/// See [docs](https://example.net/).", 9)]
+    #[test_case("    /// See [docs](https://example.net/).", 13)]
+    #[test_case("//! See [docs](https://example.net/).", 9)]
+    #[test_case("/** See [docs](https://example.net/). */", 9)]
+    #[test_case("/*! See [docs](https://example.net/). */", 9)]
+    #[test_case("#[doc = \"See [docs](https://example.net/).\"]", 14)]
+    #[test_case("#![doc = r#\"See [docs](https://example.net/).\"#]", 17)]
+    #[test_case("/// Umlaut ü [docs](https://example.net/)", 14)]
+    fn rust_doc(input: &str, column: usize) {
+        let result = rust_links(input);
+        assert_eq!(
+            vec![text_link("docs", "https://example.net/", 1, column).spanned(input)],
+            result
+        );
+    }
+
+    #[test]
+    fn rust_doc_lines() {
+        let input = "fn main() {}\n\n/// Intro\n///\n/// Read the [guide][guide].\n///\n/// [guide]: ../guide.md\nstruct S;";
+        let result = rust_links(input);
+        let expected = MarkupLink {
+            kind: LinkKind::Reference,
+            ..text_link("guide", "../guide.md", 5, 14)
+        };
+        assert_eq!(vec![expected.spanned(input)], result);
+    }
+
+    #[test]
+    fn rust_block_doc_lines() {
+        let input = "/**\n * Title\n *\n * The [readme](README.md)\n */";
+        let result = rust_links(input);
+        assert_eq!(
+            vec![text_link("readme", "README.md", 4, 8).spanned(input)],
+            result
+        );
+    }
+
+    #[test]
+    fn rust_doc_attribute_escapes() {
+        let input = "#[doc = \"\\\"quoted\\\" [docs](docs.md)\"]";
+        let result = rust_links(input);
+        assert_eq!(
+            vec![text_link("docs", "docs.md", 1, 21).spanned(input)],
+            result
+        );
+    }
+
+    #[test_case("// [comment](https://example.net/)")]
+    #[test_case("//// [comment](https://example.net/)")]
+    #[test_case("/* [comment](https://example.net/) */")]
+    #[test_case("/*** [comment](https://example.net/) */")]
+    #[test_case("let s = \"/// [string](https://example.net/)\";")]
+    #[test_case("let s = r#\"/// [string](https://example.net/)\"#;")]
+    #[test_case("let c = '\"'; let s = \"/// [string](https://example.net/)\";")]
+    #[test_case("/// ```\n/// [code](https://example.net/)\n/// ```")]
+    #[test_case("/// See [`Config`](crate::Config) and [new](Vec::new()).")]
+    #[test_case("/// The [macro](macro@vec) and [`Vec`].")]
+    #[test_case("/// The [`vec!`](vec!) macro and the [std](std) crate.")]
+    fn rust_no_doc_link(input: &str) {
+        let result = rust_links(input);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn rust_relative_file_link() {
+        let input = "/// See the [guide](guide) and [usage](usage.md).";
+        let result = rust_links(input);
+        assert_eq!(
+            vec![
+                text_link("guide", "guide", 1, 13).spanned(input),
+                text_link("usage", "usage.md", 1, 32).spanned(input)
+            ],
+            result
+        );
+    }
+
+    #[test]
+    fn rust_lifetime_is_no_literal() {
+        let input = "fn f<'a>(s: &'a str) {}\n/// [docs](docs.md)";
+        let result = rust_links(input);
+        assert_eq!(
+            vec![text_link("docs", "docs.md", 2, 5).spanned(input)],
+            result
+        );
+    }
+
+    #[test]
+    fn nested_block_comment() {
+        let input = "/* outer /* inner */ /// [no](no.md) */\n/// [yes](yes.md)";
+        let result = rust_links(input);
+        assert_eq!(
+            vec![text_link("yes", "yes.md", 2, 5).spanned(input)],
+            result
+        );
+    }
+
+    #[test_case("#", "# See [docs](docs.md)", 7)]
+    #[test_case("#", "x = 1  # See [docs](docs.md)", 14)]
+    #[test_case("//", "\tint x; // [docs](docs.md)", 12)]
+    #[test_case("/* */", "/* [docs](docs.md) */", 4)]
+    #[test_case("<!-- -->", "<!-- [docs](docs.md) -->", 6)]
+    fn markers(marker: &str, input: &str, column: usize) {
+        let result = marker_links(&[marker], input);
+        assert_eq!(
+            vec![text_link("docs", "docs.md", 1, column).spanned(input)],
+            result
+        );
+    }
+
+    #[test]
+    fn marker_block_comment_lines() {
+        let input = "int x;\n/*\n * The [docs](docs.md)\n */\nint y; /* [more](more.md) */";
+        let result = marker_links(&["//", "/* */"], input);
+        assert_eq!(
+            vec![
+                text_link("docs", "docs.md", 3, 8).spanned(input),
+                text_link("more", "more.md", 5, 11).spanned(input)
+            ],
+            result
+        );
+    }
+
+    #[test_case("#", "s = \"# [no](no.md)\"")]
+    #[test_case("#", "s = '# [no](no.md)'")]
+    #[test_case("//", "# [no](no.md)")]
+    fn marker_no_link(marker: &str, input: &str) {
+        let result = marker_links(&[marker], input);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn intra_doc_links_only_for_rust() {
+        let input = "# [guide](guide)";
+        let result = marker_links(&["#"], input);
+        assert_eq!(
+            vec![text_link("guide", "guide", 1, 3).spanned(input)],
+            result
+        );
+    }
+
+    #[test]
+    fn configured_syntax() {
+        let mut config = Config::default();
+        config.optional.comment_syntax = Some([("PY".to_string(), vec!["#".to_string()])].into());
+        assert_eq!(
+            CommentSyntax::Markers(vec!["#".to_string()]),
+            CommentSyntax::for_file("src/main.py", &config)
+        );
+        assert_eq!(
+            CommentSyntax::RustDoc,
+            CommentSyntax::for_file("src/lib.rs", &config)
+        );
+        assert_eq!(
+            CommentSyntax::RustDoc,
+            CommentSyntax::for_file("lib.rs", &Config::default())
+        );
+    }
+
+    #[test_case("/// See [docs](https://example.net/).", "https://example.net/")]
+    #[test_case("    /** ü\n     * [docs](docs.md) */", "docs.md")]
+    #[test_case("#[doc = \"See <https://example.net/>.\"]", "https://example.net/")]
+    fn span(input: &str, raw: &str) {
+        let result = SourceCodeLinkExtractor {
+            syntax: CommentSyntax::RustDoc,
+        }
+        .find_links(input);
+        assert_eq!(vec![raw], span_texts(input, &result));
+    }
+
+    #[test]
+    fn span_of_marker_comment() {
+        let input = "x = \"ü\" # See [docs](docs.md)";
+        let result = SourceCodeLinkExtractor {
+            syntax: CommentSyntax::Markers(vec!["#".to_string()]),
+        }
+        .find_links(input);
+        assert_eq!(vec!["docs.md"], span_texts(input, &result));
+        assert_eq!(22, result[0].span.start.char_column);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::link_extractors::html_link_extractor::attribute_kind;
+use crate::link_extractors::html_tokenizer::start_tags;
+use crate::link_extractors::link_extractor::line_column_from_idx;
+use crate::link_extractors::link_extractor::trimmed_range;
+use crate::link_extractors::link_extractor::LinkExtractor;
+use crate::link_extractors::link_extractor::MarkupLink;
+use crate::link_extractors::link_extractor::Span;
+use crate::link_validator::link_type::get_link_type;
+use crate::link_validator::link_type::LinkType;
+
+/// Extracts the `href` and the deprecated `xlink:href` attributes of SVG elements
+/// like `<a>`, `<image>` or `<use>`
+pub struct SvgLinkExtractor();
+
+impl LinkExtractor for SvgLinkExtractor {
+    fn find_links(&self, text: &str) -> Vec<MarkupLink> {
+        let mut result: Vec<MarkupLink> = Vec::new();
+        for tag in start_tags(text) {
+            // The `href` attribute takes precedence if an element has both
+            let Some(attribute) = ["href", "xlink:href"]
+                .iter()
+                .find_map(|name| tag.attributes.iter().find(|a| a.name == *name))
+            else {
+                continue;
+            };
+            let mut target = attribute.value.trim().to_string();
+            if target.is_empty() {
+                continue;
+            }
+            if get_link_type(&target) == LinkType::FileSystem {
+                target = url_escape::decode(&target).to_string();
+            }
+            let (line, column) = line_column_from_idx(text, tag.start);
+            result.push(MarkupLink {
+                column,
+                line,
+                target,
+                kind: attribute_kind(&tag.name, &attribute.name),
+                attribute: Some(attribute.name.clone()),
+                span: Span::new(text, trimmed_range(text, attribute.value_range.clone())),
+                ..MarkupLink::default()
+            });
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::link_extractors::link_extractor::span_texts;
+    use ntest::test_case;
+
+    fn targets(text: &str) -> Vec<(usize, usize, String)> {
+        SvgLinkExtractor()
+            .find_links(text)
+            .into_iter()
+            .map(|l| (l.line, l.column, l.target))
+            .collect()
+    }
+
+    #[test_case("<a href=\"docs/intro.md\">Intro</a>", "docs/intro.md")]
+    #[test_case(
+        "<a xlink:href='https://example.net'><text>x</text></a>",
+        "https://example.net"
+    )]
+    #[test_case("<image href=\"logo%20dark.png\" width=\"10\"/>", "logo dark.png")]
+    #[test_case("<image xlink:href=\"../img/logo.png\"/>", "../img/logo.png")]
+    #[test_case("<use href=\"#arrow\" x=\"5\"/>", "#arrow")]
+    #[test_case("<feImage href=\"texture.jpg\"/>", "texture.jpg")]
+    #[test_case("<a xlink:href=\"old.md\" href=\"new.md\">x</a>", "new.md")]
+    fn link(input: &str, target: &str) {
+        assert_eq!(vec![(1, 1, target.to_string())], targets(input));
+    }
+
+    #[test_case("<svg xmlns:xlink=\"http://www.w3.org/1999/xlink\"></svg>")]
+    #[test_case("<!-- <a href=\"x.md\">x</a> -->")]
+    #[test_case("<a href=\"\">x</a>")]
+    #[test_case("<style>a[href=\"x\"] { fill: red; }</style>")]
+    fn no_link(input: &str) {
+        assert!(targets(input).is_empty());
+    }
+
+    #[test]
+    fn positions() {
+        let input = "<svg xmlns=\"http://www.w3.org/2000/svg\">\n  <a href=\"a.md\">\n    <rect/>\n  </a><image\n    href=\"b.png\"/>\n</svg>";
+        assert_eq!(
+            vec![(2, 3, "a.md".to_string()), (4, 7, "b.png".to_string())],
+            targets(input)
+        );
+    }
+
+    #[test]
+    fn span() {
+        let input = "<text>ü</text><image xlink:href=\" logo.png \"/>";
+        let result = SvgLinkExtractor().find_links(input);
+        assert_eq!(vec!["logo.png"], span_texts(input, &result));
+        assert_eq!(35, result[0].span.start.char_column);
+    }
+}
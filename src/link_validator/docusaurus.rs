@@ -0,0 +1,217 @@
+//! Docusaurus sites, whose documents link to files with the `@site/` alias,
+//! to the routes of docs like `/docs/intro`, and to the files of the `static` directory.
+
+use crate::file_traversal::markup_type;
+use crate::link_extractors::front_matter::FrontMatter;
+use crate::link_extractors::link_extractor::MarkupLink;
+use crate::markup::MarkupType;
+use crate::Config;
+use regex::Regex;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use walkdir::WalkDir;
+
+const CONFIG_FILES: [&str; 4] = [
+    "docusaurus.config.js",
+    "docusaurus.config.ts",
+    "docusaurus.config.mjs",
+    "docusaurus.config.cjs",
+];
+
+/// Resolves the links of documents, which belong to a Docusaurus site
+#[derive(Default)]
+pub struct Docusaurus {
+    /// The site of each directory containing documents, if it belongs to one
+    sites: HashMap<PathBuf, Option<Arc<Site>>>,
+}
+
+struct Site {
+    dir: PathBuf,
+    /// The files of the docs by their route below `/docs/`
+    docs: HashMap<String, PathBuf>,
+}
+
+impl Docusaurus {
+    /// The file a link with the `@site/` alias or to a route of the site of its document points to,
+    /// or `None` if the link is resolved the usual way
+    pub fn resolve(&mut self, link: &MarkupLink, config: &Config) -> Option<String> {
+        let target = link.target.split(['#', '?']).next().unwrap_or_default();
+        let source = fs::canonicalize(&link.source).unwrap_or_else(|_| PathBuf::from(&link.source));
+        let site = self.site(&source);
+        let file = if let Some(path) = target.strip_prefix("@site/") {
+            let dir = site.map_or_else(|| default_site_dir(config), |site| site.dir.clone());
+            dir.join(path)
+        } else if let Some(route) = target
+            .strip_prefix("/docs")
+            .filter(|r| r.is_empty() || r.starts_with('/'))
+        {
+            let site = site?;
+            let route = route.trim_matches('/');
+            site.docs
+                .get(route)
+                .cloned()
+                .unwrap_or_else(|| site.dir.join("docs").join(route))
+        } else {
+            let file = site?.dir.join("static").join(target.strip_prefix('/')?);
+            file.exists().then_some(file)?
+        };
+        Some(file.to_string_lossy().to_string())
+    }
+
+    /// The site the document at `source` belongs to, which is the closest directory
+    /// with a Docusaurus config file
+    fn site(&mut self, source: &Path) -> Option<Arc<Site>> {
+        let dir = source.parent()?;
+        self.sites
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| {
+                dir.ancestors()
+                    .find(|d| CONFIG_FILES.iter().any(|f| d.join(f).is_file()))
+                    .map(|d| {
+                        info!("Found Docusaurus site at '{:?}'.", d);
+                        Arc::new(Site {
+                            dir: d.to_path_buf(),
+                            docs: doc_routes(&d.join("docs")),
+                        })
+                    })
+            })
+            .clone()
+    }
+}
+
+/// Links with the `@site/` alias outside of a site are resolved against the root directory
+fn default_site_dir(config: &Config) -> PathBuf {
+    let dir = config
+        .optional
+        .root_dir
+        .as_ref()
+        .unwrap_or(&config.directory);
+    let dir = if dir.is_file() {
+        dir.parent().unwrap_or(dir)
+    } else {
+        dir
+    };
+    fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf())
+}
+
+/// The markdown and MDX files of the docs by their route. The route consists of the directories
+/// and the file name, or the `id` of the front matter, without number prefixes like `01-`.
+/// A `slug` in the front matter replaces the route.
+fn doc_routes(docs_dir: &Path) -> HashMap<String, PathBuf> {
+    let mut routes = HashMap::new();
+    for entry in WalkDir::new(docs_dir)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|e| !e.file_type().is_dir())
+    {
+        let name = entry.file_name().to_string_lossy();
+        if markup_type(&name, &[MarkupType::Markdown, MarkupType::Mdx]).is_none() {
+            continue;
+        }
+        let Ok(relative) = entry.path().strip_prefix(docs_dir) else {
+            continue;
+        };
+        let mut segments: Vec<String> = relative
+            .parent()
+            .map(|p| {
+                p.components()
+                    .map(|c| strip_number_prefix(&c.as_os_str().to_string_lossy()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let text = fs::read_to_string(entry.path()).unwrap_or_default();
+        let front_matter = FrontMatter::find(&text);
+        let value = |key: &str| {
+            front_matter
+                .as_ref()
+                .and_then(|f| f.values(&text, &[key.to_string()]).into_iter().next())
+                .map(|(_idx, value)| value)
+        };
+        let route = if let Some(slug) = value("slug") {
+            if slug.starts_with('/') {
+                slug.trim_matches('/').to_string()
+            } else {
+                segments.push(slug.trim_matches('/').to_string());
+                segments.join("/")
+            }
+        } else {
+            let stem = name.rsplit_once('.').map_or(&*name, |(stem, _)| stem);
+            let id = value("id").unwrap_or_else(|| strip_number_prefix(stem));
+            // Index documents are served at the route of their directory
+            let is_index = ["index", "readme"].contains(&id.to_lowercase().as_str())
+                || segments.last() == Some(&id);
+            if !is_index {
+                segments.push(id);
+            }
+            segments.join("/")
+        };
+        routes.insert(route, entry.path().to_path_buf());
+    }
+    routes
+}
+
+/// Docusaurus removes number prefixes like `01-` used to order docs
+fn strip_number_prefix(name: &str) -> String {
+    lazy_static! {
+        static ref NUMBER_PREFIX_REGEX: Regex = Regex::new(r"^\d+\s*[-_.]+\s*").unwrap();
+    }
+    NUMBER_PREFIX_REGEX.replace(name, "").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntest::test_case;
+
+    fn site_file(file: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("benches")
+            .join("benchmark")
+            .join("mdx")
+            .join(file)
+    }
+
+    fn resolve(target: &str) -> Option<String> {
+        let link = MarkupLink {
+            source: site_file("docs/intro.mdx").to_string_lossy().to_string(),
+            target: target.to_string(),
+            line: 1,
+            column: 1,
+            ..MarkupLink::default()
+        };
+        Docusaurus::default().resolve(&link, &Config::default())
+    }
+
+    #[test_case("@site/src/components/Hello.js", "src/components/Hello.js")]
+    #[test_case("/docs/intro", "docs/intro.mdx")]
+    #[test_case("/docs/", "docs/index.md")]
+    #[test_case("/docs/guides/setup#install", "docs/02-guides/01-setup.md")]
+    #[test_case("/docs/guides/first-steps", "docs/02-guides/tutorial.md")]
+    #[test_case("/docs/guides", "docs/02-guides/guides.md")]
+    #[test_case("/img/logo.svg", "static/img/logo.svg")]
+    fn resolve_file(target: &str, file: &str) {
+        let expected = fs::canonicalize(site_file(file)).unwrap();
+        assert_eq!(
+            Some(expected.to_string_lossy().to_string()),
+            resolve(target)
+        );
+    }
+
+    #[test_case("./intro.mdx")]
+    #[test_case("https://docusaurus.io/docs")]
+    #[test_case("/img/missing.png")]
+    #[test_case("/documentation")]
+    fn usual_resolution(target: &str) {
+        assert_eq!(None, resolve(target));
+    }
+
+    #[test_case("1-intro", "intro")]
+    #[test_case("02 - guides", "guides")]
+    #[test_case("v2", "v2")]
+    fn number_prefix(name: &str, stripped: &str) {
+        assert_eq!(stripped, strip_number_prefix(name));
+    }
+}
@@ -1,26 +1,51 @@
+use crate::anchor_extractors::anchor_extractor::find_anchors;
+use crate::file_traversal::markup_type;
 use crate::link_validator::LinkCheckResult;
+use crate::markup::MarkupType;
 use crate::Config;
 use async_std::fs::canonicalize;
+use async_std::fs::read;
+use async_std::fs::read_to_string;
 use async_std::path::Path;
 use async_std::path::PathBuf;
+use regex::Regex;
 use std::path::MAIN_SEPARATOR;
 use std::path::MAIN_SEPARATOR_STR;
 use walkdir::WalkDir;
 
-pub async fn check_filesystem(target: &str, config: &Config) -> LinkCheckResult {
+pub async fn check_filesystem(
+    target: &str,
+    fragment: Option<&str>,
+    config: &Config,
+) -> LinkCheckResult {
     let target = Path::new(target);
     debug!("Absolute target path: '{:?}'", target);
+    let target = match find_target_file(target, config).await {
+        Ok(target) => target,
+        Err(result) => return result,
+    };
+    match fragment {
+        Some(fragment) if !fragment.is_empty() => check_fragment(&target, fragment, config).await,
+        _ => LinkCheckResult::Ok,
+    }
+}
+
+async fn find_target_file(target: &Path, config: &Config) -> Result<PathBuf, LinkCheckResult> {
     if target.exists().await {
-        return LinkCheckResult::Ok;
+        return Ok(target.to_path_buf());
     } else if !config.optional.match_file_extension.unwrap_or_default()
         && target.extension().is_none()
     {
         // Check if file exists ignoring the file extension
         let Some(target_file_name) = target.file_name() else {
-            return LinkCheckResult::Failed("Target path not found.".to_string());
+            return Err(LinkCheckResult::Failed(
+                "Target path not found.".to_string(),
+            ));
         };
         let Some(target_parent) = target.parent() else {
-            return LinkCheckResult::Failed("Target parent not found.".to_string());
+            return Err(LinkCheckResult::Failed(
+                "Target parent not found.".to_string(),
+            ));
         };
         debug!("Check if file ignoring the extension exists.");
         if target_parent.exists().await {
@@ -36,12 +61,13 @@ pub async fn check_filesystem(target: &str, config: &Config) -> LinkCheckResult
                 .filter(|e| !e.file_type().is_dir())
             {
                 let mut file_on_system = entry.into_path();
+                let found_file = file_on_system.clone();
                 file_on_system.set_extension("");
                 match file_on_system.file_name() {
                     Some(file_name) => {
                         if target_file_name == file_name {
-                            info!("Found file {:?}", file_on_system);
-                            return LinkCheckResult::Ok;
+                            info!("Found file {:?}", found_file);
+                            return Ok(found_file.into());
                         }
                     }
                     None => break,
@@ -49,16 +75,95 @@ pub async fn check_filesystem(target: &str, config: &Config) -> LinkCheckResult
             }
         }
     }
-    LinkCheckResult::Failed("Target filename not found.".to_string())
+    Err(LinkCheckResult::Failed(
+        "Target filename not found.".to_string(),
+    ))
+}
+
+async fn check_fragment(target: &Path, fragment: &str, config: &Config) -> LinkCheckResult {
+    if let Some((first_line, last_line)) = line_anchor(fragment) {
+        return check_lines(target, first_line, last_line).await;
+    }
+    let file_name = target
+        .file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let Some(markup_type) = markup_type(&file_name, &MarkupType::ALL) else {
+        warn!(
+            "The fragment part '#{}' of links to '{:?}' is not checked.",
+            fragment, target
+        );
+        return LinkCheckResult::Ok;
+    };
+    let text = match read_to_string(target).await {
+        Ok(text) => text,
+        Err(e) => {
+            return LinkCheckResult::Failed(format!(
+                "Could not read target file to check the fragment. IO Error: '{e}'."
+            ))
+        }
+    };
+    let fragment = url_escape::decode(fragment);
+    let slug_dialect = config.optional.slug_dialect.unwrap_or_default();
+    match find_anchors(&text, markup_type, slug_dialect) {
+        Some(anchors) if anchors.contains(fragment.as_ref()) => LinkCheckResult::Ok,
+        Some(_) => {
+            LinkCheckResult::Failed(format!("Fragment '#{fragment}' not found in target file."))
+        }
+        None => {
+            warn!(
+                "The fragment part '#{}' of links to '{:?}' is not checked.",
+                fragment, target
+            );
+            LinkCheckResult::Ok
+        }
+    }
+}
+
+/// Parses GitHub style line anchors like `L10` or `L10-L20`.
+pub fn line_anchor(fragment: &str) -> Option<(usize, usize)> {
+    lazy_static! {
+        static ref LINE_ANCHOR_REGEX: Regex = Regex::new(r"^L(\d+)(?:-L(\d+))?$").unwrap();
+    }
+    let captures = LINE_ANCHOR_REGEX.captures(fragment)?;
+    let first_line = captures[1].parse().ok()?;
+    let last_line = match captures.get(2) {
+        Some(last_line) => last_line.as_str().parse().ok()?,
+        None => first_line,
+    };
+    Some((first_line, last_line))
+}
+
+async fn check_lines(target: &Path, first_line: usize, last_line: usize) -> LinkCheckResult {
+    let content = match read(target).await {
+        Ok(content) => content,
+        Err(e) => {
+            return LinkCheckResult::Failed(format!(
+                "Could not read target file to check the lines. IO Error: '{e}'."
+            ))
+        }
+    };
+    check_line_range(&content, first_line, last_line)
+}
+
+/// Checks that the lines of a line anchor are within the `content` of a file.
+pub fn check_line_range(content: &[u8], first_line: usize, last_line: usize) -> LinkCheckResult {
+    let num_lines = String::from_utf8_lossy(content).lines().count();
+    if first_line == 0 || last_line == 0 {
+        LinkCheckResult::Failed("Line numbers start at 1.".to_string())
+    } else if first_line.max(last_line) > num_lines {
+        LinkCheckResult::Failed(format!(
+            "Line {} is beyond the end of the file, which has {num_lines} lines.",
+            first_line.max(last_line)
+        ))
+    } else {
+        LinkCheckResult::Ok
+    }
 }
 
 pub async fn resolve_target_link(source: &str, target: &str, config: &Config) -> String {
     let mut normalized_link = target.replace(['/', '\\'], MAIN_SEPARATOR_STR);
     if let Some(idx) = normalized_link.find('#') {
-        warn!(
-            "Strip everything after #. The chapter (aka anchor aka fragment) part '{}' is not checked.",
-            &normalized_link[idx..]
-        );
         normalized_link = normalized_link[..idx].to_string();
     }
     let mut fs_link_target = Path::new(&normalized_link).to_path_buf();
@@ -78,10 +183,26 @@ pub async fn resolve_target_link(source: &str, target: &str, config: &Config) ->
         .to_str()
         .unwrap_or_else(|| panic!("Could not resolve target path '{}' ", target))
         .to_string();
-    // Remove verbatim path identifier which causes trouble on windows when using ../../ in paths
+    strip_verbatim_prefix(&abs_path)
+}
+
+/// Resolves the absolute path of the document containing a link.
+pub async fn resolve_source_link(source: &str) -> String {
+    let abs_path = canonicalize(source)
+        .await
+        .unwrap_or_else(|_| panic!("Path '{}' does not exist.", source))
+        .to_str()
+        .unwrap_or_else(|| panic!("Could not resolve source path '{}' ", source))
+        .to_string();
+    strip_verbatim_prefix(&abs_path)
+}
+
+/// Removes the verbatim path identifier,
+/// which causes trouble on windows when using ../../ in paths.
+fn strip_verbatim_prefix(abs_path: &str) -> String {
     abs_path
         .strip_prefix(r"\\?\")
-        .unwrap_or(&abs_path)
+        .unwrap_or(abs_path)
         .to_string()
 }
 
@@ -106,6 +227,118 @@ async fn absolute_target_path(source: &str, target: &PathBuf) -> PathBuf {
 #[cfg(test)]
 mod test {
     use super::*;
+    use ntest::test_case;
+
+    fn benchmark_file(file: &str) -> String {
+        benchmark_path("markdown", file)
+    }
+
+    fn benchmark_path(dir: &str, file: &str) -> String {
+        Path::new(file!())
+            .parent()
+            .unwrap()
+            .parent()
+            .unwrap()
+            .parent()
+            .unwrap()
+            .join("benches")
+            .join("benchmark")
+            .join(dir)
+            .join(file)
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test_case("anchor_links.md", "auto-anchor")]
+    #[test_case("anchor_links.md", "auto-anchor-_3")]
+    #[test_case("anchor_links.md", "manual-anchor-man-anch-1")]
+    #[test_case("HashLinks.md", "chapter-22")]
+    #[test_case("anchor_links_2", "2nd-auto-anchor-2")]
+    #[test_case(
+        "withUmlaut_ö/LinksWithUmläuts.md",
+        "torrentur-suum-abstrahor-quique-iuppiter-rerum-mediocris"
+    )]
+    fn existing_fragment(file: &str, fragment: &str) {
+        let result = async_std::task::block_on(check_filesystem(
+            &benchmark_file(file),
+            Some(fragment),
+            &Config::default(),
+        ));
+        assert_eq!(result, LinkCheckResult::Ok);
+    }
+
+    #[test_case("anchor_links.md", "1st-auto-anchor")]
+    #[test_case("anchor_links.md", "auto-anchor-3")]
+    #[test_case("anchor_links.md", "man-anch-1")]
+    #[test_case("HashLinks.md", "####chapter-22")]
+    fn missing_fragment(file: &str, fragment: &str) {
+        let result = async_std::task::block_on(check_filesystem(
+            &benchmark_file(file),
+            Some(fragment),
+            &Config::default(),
+        ));
+        assert!(result.is_err());
+    }
+
+    #[test_case("anchor_links.html", "auto-anchor-_3", true)]
+    #[test_case("anchor_links_2.html", "nd-manual-anchor-1st-man-anch-1", true)]
+    #[test_case("anchor_links_2.html", "2nd-auto-anchor", false)]
+    #[test_case("anchor_links.html", "1st-auto-anchor", false)]
+    #[test_case("anchor_links_2", "html-anchors", true)]
+    fn html_fragment(file: &str, fragment: &str, exists: bool) {
+        let result = async_std::task::block_on(check_filesystem(
+            &benchmark_path("html", file),
+            Some(fragment),
+            &Config::default(),
+        ));
+        assert_eq!(result == LinkCheckResult::Ok, exists);
+    }
+
+    #[test_case("L1", 1, 1)]
+    #[test_case("L120-L140", 120, 140)]
+    #[test_case("L20-L10", 20, 10)]
+    fn line_anchors(fragment: &str, first_line: usize, last_line: usize) {
+        assert_eq!(line_anchor(fragment), Some((first_line, last_line)));
+    }
+
+    #[test_case("l10")]
+    #[test_case("L10-20")]
+    #[test_case("L10C5")]
+    #[test_case("Lorem")]
+    fn no_line_anchors(fragment: &str) {
+        assert_eq!(line_anchor(fragment), None);
+    }
+
+    #[test_case("L1", true)]
+    #[test_case("L21", true)]
+    #[test_case("L3-L21", true)]
+    #[test_case("L22", false)]
+    #[test_case("L5-L22", false)]
+    #[test_case("L0", false)]
+    fn line_fragment(fragment: &str, exists: bool) {
+        // The file has 21 lines, the last one not being terminated by a line break
+        let result = async_std::task::block_on(check_filesystem(
+            &benchmark_file("HashLinks.md"),
+            Some(fragment),
+            &Config::default(),
+        ));
+        assert_eq!(result == LinkCheckResult::Ok, exists);
+    }
+
+    #[tokio::test]
+    async fn fragment_of_not_existing_file() {
+        let result = check_filesystem(
+            &benchmark_file("not_existing.md"),
+            Some("auto-anchor"),
+            &Config::default(),
+        )
+        .await;
+        assert_eq!(
+            result,
+            LinkCheckResult::Failed("Target filename not found.".to_string())
+        );
+    }
 
     #[tokio::test]
     async fn remove_dot() {
@@ -1,11 +1,38 @@
+use crate::anchor_extractors::anchor_extractor::find_anchors;
+use crate::anchor_extractors::slug::SlugDialect;
+use crate::link_validator::file_system::check_line_range;
+use crate::link_validator::file_system::line_anchor;
 use crate::link_validator::LinkCheckResult;
+use crate::link_validator::Severity;
+use crate::markup::MarkupType;
 
 use reqwest::header::ACCEPT;
+use reqwest::header::CONTENT_TYPE;
 use reqwest::header::USER_AGENT;
 use reqwest::Client;
 use reqwest::Method;
 use reqwest::Request;
 use reqwest::StatusCode;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::sync::Mutex;
+use tokio::sync::OnceCell;
+
+/// The outcome of fetching a document, and the anchors found in it, if its type is known
+type FetchedDocument = (LinkCheckResult, Option<HashSet<String>>);
+
+lazy_static! {
+    static ref CLIENT: Client = reqwest::Client::builder()
+        .brotli(true)
+        .gzip(true)
+        .deflate(true)
+        .build()
+        .expect("Bug! failed to build client");
+    /// Documents fetched to check fragments, so each URL is only requested once
+    static ref DOCUMENTS: Mutex<HashMap<reqwest::Url, Arc<OnceCell<FetchedDocument>>>> =
+        Mutex::new(HashMap::new());
+}
 
 pub async fn check_http(target: &str) -> LinkCheckResult {
     debug!("Checking http link target '{:?}' ...", target);
@@ -17,6 +44,161 @@ pub async fn check_http(target: &str) -> LinkCheckResult {
     }
 }
 
+/// Checks that the document at `target` exists and contains an anchor for `fragment`.
+pub async fn check_http_fragment(
+    target: &str,
+    fragment: &str,
+    severity: Severity,
+    slug_dialect: SlugDialect,
+) -> LinkCheckResult {
+    debug!(
+        "Checking http link target '{:?}' with fragment '{:?}' ...",
+        target, fragment
+    );
+    let url = reqwest::Url::parse(target).expect("URL of unknown type");
+    if let (Some((first_line, last_line)), Some(raw_url)) =
+        (line_anchor(fragment), github_raw_url(&url))
+    {
+        return check_http_lines(&raw_url, first_line, last_line, severity).await;
+    }
+
+    let document = DOCUMENTS
+        .lock()
+        .expect("Bug! documents lock poisoned")
+        .entry(url.clone())
+        .or_default()
+        .clone();
+    let (result, anchors) = document
+        .get_or_init(|| async {
+            fetch_document(&url, slug_dialect)
+                .await
+                .unwrap_or_else(|error_msg| {
+                    (
+                        LinkCheckResult::Failed(format!("Http(s) request failed: {error_msg}")),
+                        None,
+                    )
+                })
+        })
+        .await;
+
+    let fragment = url_escape::decode(fragment);
+    match anchors {
+        Some(anchors) if !contains_anchor(anchors, &fragment) => {
+            severity.to_result(format!("Fragment '#{fragment}' not found in the document."))
+        }
+        Some(_) | None => result.clone(),
+    }
+}
+
+/// Checks the lines of a line anchor like `#L10-L20` against the raw content of a file on GitHub.
+async fn check_http_lines(
+    raw_url: &reqwest::Url,
+    first_line: usize,
+    last_line: usize,
+    severity: Severity,
+) -> LinkCheckResult {
+    let response = match CLIENT.execute(new_request(Method::GET, raw_url)).await {
+        Ok(response) => response,
+        Err(error) => return LinkCheckResult::Failed(format!("Http(s) request failed: {error}")),
+    };
+    let status = response.status();
+    if !status.is_success() {
+        return LinkCheckResult::Failed(status_to_string(status));
+    }
+    match response.bytes().await {
+        Ok(content) => {
+            let result = check_line_range(&content, first_line, last_line);
+            if result.is_err() {
+                severity.to_result(result.msg().to_string())
+            } else {
+                result
+            }
+        }
+        Err(error) => LinkCheckResult::Failed(format!("Http(s) request failed: {error}")),
+    }
+}
+
+/// The URL of the raw content of a file which is shown by a GitHub URL like
+/// `https://github.com/<owner>/<repo>/blob/<ref>/<path>`.
+/// The line anchors of those pages are resolved by a script, so they are not part of the HTML.
+fn github_raw_url(url: &reqwest::Url) -> Option<reqwest::Url> {
+    if url.host_str() != Some("github.com") {
+        return None;
+    }
+    let segments: Vec<&str> = url.path_segments()?.collect();
+    match segments.as_slice() {
+        [owner, repo, "blob", git_ref, path @ ..] if !path.is_empty() => {
+            reqwest::Url::parse(&format!(
+                "https://raw.githubusercontent.com/{owner}/{repo}/{git_ref}/{}",
+                path.join("/")
+            ))
+            .ok()
+        }
+        _ => None,
+    }
+}
+
+async fn fetch_document(
+    url: &reqwest::Url,
+    slug_dialect: SlugDialect,
+) -> reqwest::Result<FetchedDocument> {
+    let response = CLIENT.execute(new_request(Method::GET, url)).await?;
+    let status = response.status();
+    if !status.is_success() {
+        return Ok((LinkCheckResult::Failed(status_to_string(status)), None));
+    }
+    let result = if response.url() == url {
+        LinkCheckResult::Ok
+    } else {
+        LinkCheckResult::Warning("Request was redirected to ".to_string() + response.url().as_ref())
+    };
+    let markup_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .and_then(content_markup_type);
+    if let Some(markup_type) = markup_type {
+        let text = response.text().await?;
+        Ok((result, find_anchors(&text, markup_type, slug_dialect)))
+    } else {
+        debug!(
+            "Unknown content type of '{}'. The fragment is not checked.",
+            url
+        );
+        Ok((result, None))
+    }
+}
+
+/// Identifies the markup type of a document served with the given `Content-Type` header.
+fn content_markup_type(content_type: &str) -> Option<MarkupType> {
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase();
+    match mime.as_str() {
+        "text/html" | "application/xhtml+xml" => Some(MarkupType::Html),
+        "text/markdown" | "text/x-markdown" => Some(MarkupType::Markdown),
+        _ => None,
+    }
+}
+
+/// Checks if `fragment` points to one of the `anchors`.
+/// GitHub prefixes the ids of rendered documents with `user-content-`,
+/// and resolves the un-prefixed fragments with a script.
+fn contains_anchor(anchors: &HashSet<String>, fragment: &str) -> bool {
+    anchors.contains(fragment) || anchors.contains(&format!("user-content-{fragment}"))
+}
+
+fn status_to_string(status: StatusCode) -> String {
+    format!(
+        "{} - {}",
+        status.as_str(),
+        status.canonical_reason().unwrap_or("Unknown reason")
+    )
+}
+
 fn new_request(method: Method, url: &reqwest::Url) -> Request {
     let mut req = Request::new(method, url.clone());
     let headers = req.headers_mut();
@@ -26,23 +208,6 @@ fn new_request(method: Method, url: &reqwest::Url) -> Request {
 }
 
 async fn http_request(url: &reqwest::Url) -> reqwest::Result<LinkCheckResult> {
-    lazy_static! {
-        static ref CLIENT: Client = reqwest::Client::builder()
-            .brotli(true)
-            .gzip(true)
-            .deflate(true)
-            .build()
-            .expect("Bug! failed to build client");
-    }
-
-    fn status_to_string(status: StatusCode) -> String {
-        format!(
-            "{} - {}",
-            status.as_str(),
-            status.canonical_reason().unwrap_or("Unknown reason")
-        )
-    }
-
     let head_request = new_request(Method::HEAD, url);
     let get_request = new_request(Method::GET, url);
 
@@ -86,6 +251,7 @@ async fn http_request(url: &reqwest::Url) -> reqwest::Result<LinkCheckResult> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use ntest::test_case;
 
     #[tokio::test]
     async fn check_http_is_available() {
@@ -136,6 +302,97 @@ mod test {
         );
     }
 
+    #[tokio::test]
+    async fn check_http_request_with_existing_fragment() {
+        let result = check_http_fragment(
+            "https://github.com/becheran/mlc",
+            "install-locally",
+            Severity::Fail,
+            SlugDialect::GitHub,
+        )
+        .await;
+        assert_eq!(result, LinkCheckResult::Ok);
+    }
+
+    #[tokio::test]
+    async fn check_http_request_with_missing_fragment() {
+        let result = check_http_fragment(
+            "https://github.com/becheran/mlc",
+            "bla",
+            Severity::Warn,
+            SlugDialect::GitHub,
+        )
+        .await;
+        assert_eq!(
+            result,
+            LinkCheckResult::Warning("Fragment '#bla' not found in the document.".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn check_http_request_with_line_anchor() {
+        let result = check_http_fragment(
+            "https://github.com/becheran/mlc/blob/master/README.md",
+            "L1-L2",
+            Severity::Fail,
+            SlugDialect::GitHub,
+        )
+        .await;
+        assert_eq!(result, LinkCheckResult::Ok);
+    }
+
+    #[test_case(
+        "https://github.com/becheran/mlc/blob/master/src/lib.rs",
+        "https://raw.githubusercontent.com/becheran/mlc/master/src/lib.rs"
+    )]
+    #[test_case(
+        "https://github.com/becheran/mlc/blob/v0.17.1/benches/benchmark/markdown/ref_links.md",
+        "https://raw.githubusercontent.com/becheran/mlc/v0.17.1/benches/benchmark/markdown/ref_links.md"
+    )]
+    fn github_blob_url(url: &str, raw_url: &str) {
+        let url = reqwest::Url::parse(url).unwrap();
+        assert_eq!(github_raw_url(&url).unwrap().as_str(), raw_url);
+    }
+
+    #[test_case("https://github.com/becheran/mlc")]
+    #[test_case("https://github.com/becheran/mlc/blob/master")]
+    #[test_case("https://github.com/becheran/mlc/tree/master/src")]
+    #[test_case("https://gitlab.com/becheran/mlc/blob/master/src/lib.rs")]
+    fn no_github_blob_url(url: &str) {
+        let url = reqwest::Url::parse(url).unwrap();
+        assert_eq!(github_raw_url(&url), None);
+    }
+
+    #[test_case("text/html")]
+    #[test_case("text/html; charset=utf-8")]
+    #[test_case("application/xhtml+xml")]
+    fn html_content_type(content_type: &str) {
+        assert_eq!(content_markup_type(content_type), Some(MarkupType::Html));
+    }
+
+    #[test_case("text/markdown")]
+    #[test_case("text/markdown; charset=UTF-8")]
+    fn markdown_content_type(content_type: &str) {
+        assert_eq!(
+            content_markup_type(content_type),
+            Some(MarkupType::Markdown)
+        );
+    }
+
+    #[test_case("text/plain")]
+    #[test_case("image/png")]
+    fn unknown_content_type(content_type: &str) {
+        assert_eq!(content_markup_type(content_type), None);
+    }
+
+    #[test]
+    fn github_user_content_anchor() {
+        let anchors = HashSet::from(["user-content-install".to_string(), "top".to_string()]);
+        assert!(contains_anchor(&anchors, "install"));
+        assert!(contains_anchor(&anchors, "top"));
+        assert!(!contains_anchor(&anchors, "usage"));
+    }
+
     #[tokio::test]
     async fn check_wrong_http_request() {
         let result = check_http("https://doesNotExist.me/even/less/likelly").await;
@@ -0,0 +1,232 @@
+//! LaTeX documents, whose file links TeX resolves against the directory of the main document.
+//! Documents added with `\input` or `\include` are part of their main document.
+//! Graphics are also searched in the directories of `\graphicspath`,
+//! and without an extension with the extensions graphicx tries.
+
+use crate::file_traversal::markup_type;
+use crate::link_extractors::latex_link_extractor::graphics_paths;
+use crate::link_extractors::link_extractor::LinkKind;
+use crate::link_extractors::link_extractor::MarkupLink;
+use crate::link_validator::get_link_type;
+use crate::link_validator::LinkType;
+use crate::markup::MarkupType;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The extensions graphicx tries in turn for graphics without one, like pdfLaTeX does
+const GRAPHICS_EXTENSIONS: [&str; 4] = ["pdf", "png", "jpg", "eps"];
+
+/// Resolves the file links of LaTeX documents
+#[derive(Default)]
+pub struct Latex {
+    /// The documents by their canonical path
+    documents: HashMap<PathBuf, Document>,
+}
+
+struct Document {
+    /// The directory of the main document
+    main_dir: PathBuf,
+    /// The directories of `\graphicspath`, relative to the directory of the main document
+    graphics_paths: Vec<String>,
+}
+
+impl Latex {
+    /// Finds the main document of the LaTeX documents among the sources of `links`.
+    /// A document added by no other document is a main document.
+    #[must_use]
+    pub fn new(links: &[MarkupLink]) -> Self {
+        // The documents each document adds with `\input` or `\include`, as written
+        let mut inputs: HashMap<PathBuf, Vec<&str>> = HashMap::new();
+        for link in links {
+            if markup_type(&link.source, &[MarkupType::Latex]).is_none() {
+                continue;
+            }
+            let Ok(source) = fs::canonicalize(&link.source) else {
+                continue;
+            };
+            let targets = inputs.entry(source).or_default();
+            if link.kind == LinkKind::Inline
+                && markup_type(&link.target, &[MarkupType::Latex]).is_some()
+                && get_link_type(&link.target) == LinkType::FileSystem
+            {
+                targets.push(&link.target);
+            }
+        }
+
+        let added: HashMap<&PathBuf, HashSet<PathBuf>> = inputs
+            .keys()
+            .map(|document| (document, added_documents(document, &inputs)))
+            .collect();
+        let mut main_documents: HashMap<PathBuf, &PathBuf> = HashMap::new();
+        for (main, documents) in &added {
+            if added
+                .iter()
+                .any(|(other, d)| other != main && d.contains(*main))
+            {
+                continue;
+            }
+            for document in documents {
+                main_documents.insert(document.clone(), main);
+            }
+        }
+
+        let mut documents = HashMap::new();
+        for path in inputs.keys() {
+            let main = main_documents.get(path).copied().unwrap_or(path);
+            let graphics_paths = [path, main]
+                .iter()
+                .map(|document| graphics_paths(&fs::read_to_string(document).unwrap_or_default()))
+                .find(|dirs| !dirs.is_empty())
+                .unwrap_or_default();
+            let document = Document {
+                main_dir: main.parent().map(Path::to_path_buf).unwrap_or_default(),
+                graphics_paths,
+            };
+            documents.insert(path.clone(), document);
+        }
+        Self { documents }
+    }
+
+    /// The file a relative link of a LaTeX document points to,
+    /// or `None` if the link is resolved the usual way
+    #[must_use]
+    pub fn resolve(&self, link: &MarkupLink) -> Option<String> {
+        if get_link_type(&link.target) != LinkType::FileSystem
+            || Path::new(&link.target).has_root()
+            || markup_type(&link.source, &[MarkupType::Latex]).is_none()
+        {
+            return None;
+        }
+        let source = fs::canonicalize(&link.source).ok()?;
+        let document = self.documents.get(&source)?;
+        let target = link.target.split('#').next().unwrap_or_default();
+        if link.kind != LinkKind::Image {
+            let file = document.main_dir.join(target);
+            return Some(file.to_string_lossy().to_string());
+        }
+
+        let names: Vec<String> = if Path::new(target).extension().is_some() {
+            vec![target.to_string()]
+        } else {
+            GRAPHICS_EXTENSIONS
+                .iter()
+                .map(|extension| format!("{target}.{extension}"))
+                .collect()
+        };
+        let dirs: Vec<PathBuf> = std::iter::once(document.main_dir.clone())
+            .chain(
+                document
+                    .graphics_paths
+                    .iter()
+                    .map(|dir| document.main_dir.join(dir)),
+            )
+            .collect();
+        // graphicx tries each extension in all directories before the next one
+        let file = names
+            .iter()
+            .flat_map(|name| dirs.iter().map(move |dir| dir.join(name)))
+            .find(|file| file.is_file())
+            .unwrap_or_else(|| document.main_dir.join(&names[0]));
+        Some(file.to_string_lossy().to_string())
+    }
+}
+
+/// The documents `document` adds, directly or through other documents.
+/// All of them are resolved against the directory of `document`.
+fn added_documents(document: &Path, inputs: &HashMap<PathBuf, Vec<&str>>) -> HashSet<PathBuf> {
+    let dir = document.parent().unwrap_or(document);
+    let mut added = HashSet::new();
+    let mut pending = vec![document.to_path_buf()];
+    while let Some(path) = pending.pop() {
+        for target in inputs.get(&path).into_iter().flatten() {
+            let Ok(file) = fs::canonicalize(dir.join(target)) else {
+                continue;
+            };
+            if file != document && added.insert(file.clone()) {
+                pending.push(file);
+            }
+        }
+    }
+    added
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntest::test_case;
+
+    fn latex_file(file: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("benches")
+            .join("benchmark")
+            .join("latex")
+            .join(file)
+    }
+
+    fn latex_link(source: &str, target: &str, kind: LinkKind) -> MarkupLink {
+        MarkupLink {
+            source: latex_file(source).to_string_lossy().to_string(),
+            target: target.to_string(),
+            line: 1,
+            column: 1,
+            kind,
+            ..MarkupLink::default()
+        }
+    }
+
+    fn resolve(source: &str, target: &str, kind: LinkKind) -> Option<String> {
+        let links = [
+            latex_link("paper.tex", "chapters/intro.tex", LinkKind::Inline),
+            latex_link("chapters/intro.tex", "logo", LinkKind::Image),
+        ];
+        Latex::new(&links).resolve(&latex_link(source, target, kind))
+    }
+
+    fn canonical(file: &str) -> String {
+        fs::canonicalize(latex_file(file))
+            .unwrap()
+            .to_string_lossy()
+            .to_string()
+    }
+
+    #[test_case(
+        "paper.tex",
+        "../link_kinds/images/logo",
+        "../link_kinds/images/logo.png"
+    )]
+    #[test_case("paper.tex", "logo", "../link_kinds/images/logo.png")]
+    #[test_case("chapters/intro.tex", "logo", "../link_kinds/images/logo.png")]
+    #[test_case("chapters/intro.tex", "logo.png", "../link_kinds/images/logo.png")]
+    fn graphic(source: &str, target: &str, file: &str) {
+        let resolved = resolve(source, target, LinkKind::Image).unwrap();
+        assert_eq!(
+            canonical(file),
+            fs::canonicalize(resolved).unwrap().to_string_lossy()
+        );
+    }
+
+    #[test]
+    fn missing_graphic() {
+        let resolved = resolve("chapters/intro.tex", "missing", LinkKind::Image).unwrap();
+        assert_eq!(
+            Path::new(&canonical("paper.tex")).with_file_name("missing.pdf"),
+            Path::new(&resolved)
+        );
+    }
+
+    #[test_case("refs.bib", "refs.bib")]
+    #[test_case("paper.tex#intro", "paper.tex")]
+    fn main_document_dir(target: &str, file: &str) {
+        let resolved = resolve("chapters/intro.tex", target, LinkKind::Inline).unwrap();
+        assert_eq!(Path::new(&canonical(file)), Path::new(&resolved));
+    }
+
+    #[test_case("paper.tex", "https://example.net/logo.png")]
+    #[test_case("paper.tex", "/logo.png")]
+    #[test_case("../markdown/ignore_me.md", "logo.png")]
+    fn usual_resolution(source: &str, target: &str) {
+        assert_eq!(None, resolve(source, target, LinkKind::Image));
+    }
+}
@@ -9,6 +9,8 @@ pub enum LinkType {
     Ftp,
     Mail,
     FileSystem,
+    /// Link to a fragment of the document containing the link, like `#usage`
+    SameDocument,
     UnknownUrlSchema,
 }
 
@@ -19,6 +21,10 @@ pub fn get_link_type(link: &str) -> LinkType {
             Regex::new(r"^(([[:alpha:]]:(\\|/))|(..?(\\|/))|((\\\\?|//?))).*").unwrap();
     }
 
+    if link.starts_with('#') {
+        return LinkType::SameDocument;
+    }
+
     if FILE_SYSTEM_REGEX.is_match(link) || !link.contains(':') {
         return if link.contains('@') {
             LinkType::Mail
@@ -77,4 +83,11 @@ mod tests {
     fn test_file_system_link_types(link: &str) {
         test_link(link, &LinkType::FileSystem);
     }
+
+    #[test_case("#usage")]
+    #[test_case("#")]
+    #[test_case("#some:thing@else")]
+    fn same_document_link_types(link: &str) {
+        test_link(link, &LinkType::SameDocument);
+    }
 }
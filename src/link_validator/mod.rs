@@ -1,19 +1,31 @@
+mod docusaurus;
 mod file_system;
 mod http;
+mod latex;
 mod mail;
+mod wiki;
 
 pub mod link_type;
 
+use crate::link_extractors::link_extractor::LinkIssue;
 use crate::link_extractors::link_extractor::MarkupLink;
 use crate::link_validator::file_system::check_filesystem;
 use crate::link_validator::http::check_http;
+use crate::link_validator::http::check_http_fragment;
 use crate::Config;
 use colored::ColoredString;
 use colored::Colorize;
 use mail::check_mail;
+use serde::Deserialize;
+use std::fmt;
+use std::str::FromStr;
+use url::Url;
 
+pub use docusaurus::Docusaurus;
+pub use latex::Latex;
 pub use link_type::get_link_type;
 pub use link_type::LinkType;
+pub use wiki::Vault;
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub enum LinkCheckResult {
@@ -24,6 +36,45 @@ pub enum LinkCheckResult {
     NotImplemented(String),
 }
 
+/// How a detected problem which is not necessarily fatal is reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum Severity {
+    Warn,
+    Fail,
+}
+
+impl FromStr for Severity {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "warn" => Ok(Self::Warn),
+            "fail" => Ok(Self::Fail),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::Warn => "warn",
+            Self::Fail => "fail",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl Severity {
+    #[must_use]
+    pub const fn to_result(self, msg: String) -> LinkCheckResult {
+        match self {
+            Self::Warn => LinkCheckResult::Warning(msg),
+            Self::Fail => LinkCheckResult::Failed(msg),
+        }
+    }
+}
+
 impl LinkCheckResult {
     #[must_use]
     pub fn msg(&self) -> &'_ str {
@@ -66,19 +117,107 @@ impl LinkCheckResult {
     }
 }
 
+/// The type of a link. Relative paths become web links if the base of their document is a URL.
+#[must_use]
+pub fn get_link_type_with_base(link: &MarkupLink) -> LinkType {
+    let link_type = get_link_type(&link.target);
+    let base_type = link.base.as_deref().map(get_link_type);
+    if link_type == LinkType::FileSystem && base_type == Some(LinkType::Http) {
+        LinkType::Http
+    } else {
+        link_type
+    }
+}
+
 pub async fn resolve_target_link(
     link: &MarkupLink,
     link_type: &LinkType,
     config: &Config,
 ) -> String {
-    if link_type == &LinkType::FileSystem {
-        file_system::resolve_target_link(&link.source, &link.target, config).await
+    let target = with_base(link);
+    match link_type {
+        LinkType::FileSystem => {
+            file_system::resolve_target_link(&link.source, &target, config).await
+        }
+        LinkType::SameDocument => file_system::resolve_source_link(&link.source).await,
+        LinkType::Http if config.optional.http_fragments.is_some() => target
+            .split_once('#')
+            .map_or(target.as_str(), |(url, _fragment)| url)
+            .to_string(),
+        LinkType::Http | LinkType::Ftp | LinkType::Mail | LinkType::UnknownUrlSchema => target,
+    }
+}
+
+/// Resolves a relative link against the base of its document, like a browser does.
+/// Links to fragments of the same document, like `#usage`, still point to the document.
+fn with_base(link: &MarkupLink) -> String {
+    let Some(base) = link.base.as_deref() else {
+        return link.target.clone();
+    };
+    if get_link_type(&link.target) != LinkType::FileSystem {
+        return link.target.clone();
+    }
+    let base_type = get_link_type(base);
+    if base_type == LinkType::Http {
+        Url::parse(base)
+            .and_then(|base| base.join(&link.target))
+            .map_or_else(|_| link.target.clone(), String::from)
+    } else if base_type == LinkType::FileSystem && !link.target.starts_with(['/', '\\']) {
+        // The last segment of the base is a file name, like in `docs/index.html`
+        let dir = base.rfind('/').map_or("", |idx| &base[..=idx]);
+        format!("{dir}{}", link.target)
     } else {
-        link.target.to_string()
+        link.target.clone()
     }
 }
 
-pub async fn check(link_target: &str, link_type: &LinkType, config: &Config) -> LinkCheckResult {
+/// The result of a link with a problem found during extraction
+#[must_use]
+pub fn issue_result(issue: &LinkIssue, config: &Config) -> LinkCheckResult {
+    match issue {
+        LinkIssue::UndefinedReference(label) => {
+            LinkCheckResult::Failed(format!("Reference '[{label}]' is not defined."))
+        }
+        LinkIssue::UnusedDefinition(label) => config
+            .optional
+            .unused_references
+            .unwrap_or(Severity::Warn)
+            .to_result(format!("Reference definition '[{label}]' is never used.")),
+        LinkIssue::UndefinedLabel(label) => {
+            LinkCheckResult::Failed(format!("Label '{label}' is not defined in the document."))
+        }
+        LinkIssue::MissingAttachment(name) => {
+            LinkCheckResult::Failed(format!("Attachment '{name}' is not part of the cell."))
+        }
+        LinkIssue::Suppressed(directive) => {
+            LinkCheckResult::Ignored(format!("Ignore link because of {directive}."))
+        }
+    }
+}
+
+/// Extracts the fragment (aka anchor) part of a link, if it is one that gets checked.
+#[must_use]
+pub fn get_fragment(link: &MarkupLink, link_type: &LinkType, config: &Config) -> Option<String> {
+    let checked = match link_type {
+        LinkType::FileSystem | LinkType::SameDocument => true,
+        LinkType::Http => config.optional.http_fragments.is_some(),
+        LinkType::Ftp | LinkType::Mail | LinkType::UnknownUrlSchema => false,
+    };
+    if checked {
+        link.target
+            .find('#')
+            .map(|idx| link.target[idx + 1..].to_string())
+    } else {
+        None
+    }
+}
+
+pub async fn check(
+    link_target: &str,
+    link_type: &LinkType,
+    fragment: Option<&str>,
+    config: &Config,
+) -> LinkCheckResult {
     info!("Checking link '{}' ...", &link_target);
     match link_type {
         LinkType::Ftp | LinkType::UnknownUrlSchema => LinkCheckResult::NotImplemented(format!(
@@ -90,9 +229,119 @@ pub async fn check(link_target: &str, link_type: &LinkType, config: &Config) ->
             if config.optional.offline.unwrap_or_default() {
                 LinkCheckResult::Ignored("Ignore web link because of the offline flag.".to_string())
             } else {
-                check_http(link_target).await
+                match (fragment, config.optional.http_fragments) {
+                    (Some(fragment), Some(severity)) if !fragment.is_empty() => {
+                        let slug_dialect = config.optional.slug_dialect.unwrap_or_default();
+                        check_http_fragment(link_target, fragment, severity, slug_dialect).await
+                    }
+                    _ => check_http(link_target).await,
+                }
             }
         }
-        LinkType::FileSystem => check_filesystem(link_target, config).await,
+        LinkType::FileSystem | LinkType::SameDocument => {
+            check_filesystem(link_target, fragment, config).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntest::test_case;
+
+    fn link(target: &str, base: Option<&str>) -> MarkupLink {
+        MarkupLink {
+            source: "index.html".to_string(),
+            target: target.to_string(),
+            line: 1,
+            column: 1,
+            base: base.map(ToString::to_string),
+            ..MarkupLink::default()
+        }
+    }
+
+    #[test_case("page.html", "", "page.html")]
+    #[test_case("page.html", "docs/v2/", "docs/v2/page.html")]
+    #[test_case("page.html", "../docs/index.html", "../docs/page.html")]
+    #[test_case("page.html", "index.html", "page.html")]
+    #[test_case("/page.html", "docs/", "/page.html")]
+    #[test_case("#top", "docs/", "#top")]
+    #[test_case("https://example.net/", "docs/", "https://example.net/")]
+    #[test_case(
+        "page.html#a",
+        "https://example.net/docs/",
+        "https://example.net/docs/page.html#a"
+    )]
+    #[test_case(
+        "../page.html",
+        "https://example.net/docs/",
+        "https://example.net/page.html"
+    )]
+    #[test_case(
+        "/page.html",
+        "https://example.net/docs/",
+        "https://example.net/page.html"
+    )]
+    fn base(target: &str, base: &str, expected: &str) {
+        let base = Some(base).filter(|b| !b.is_empty());
+        assert_eq!(expected, with_base(&link(target, base)));
+    }
+
+    #[test]
+    fn link_type_with_base() {
+        let web_base = Some("https://example.net/docs/");
+        assert_eq!(
+            LinkType::Http,
+            get_link_type_with_base(&link("page.html", web_base))
+        );
+        assert_eq!(
+            LinkType::SameDocument,
+            get_link_type_with_base(&link("#top", web_base))
+        );
+        assert_eq!(
+            LinkType::FileSystem,
+            get_link_type_with_base(&link("page.html", Some("docs/")))
+        );
+        assert_eq!(
+            LinkType::FileSystem,
+            get_link_type_with_base(&link("page.html", None))
+        );
+    }
+
+    #[test]
+    fn issue_results() {
+        let mut config = Config::default();
+        let undefined = LinkIssue::UndefinedReference("ref".to_string());
+        let unused = LinkIssue::UnusedDefinition("ref".to_string());
+        assert_eq!(
+            LinkCheckResult::Failed("Reference '[ref]' is not defined.".to_string()),
+            issue_result(&undefined, &config)
+        );
+        assert_eq!(
+            LinkCheckResult::Warning("Reference definition '[ref]' is never used.".to_string()),
+            issue_result(&unused, &config)
+        );
+        config.optional.unused_references = Some(Severity::Fail);
+        assert_eq!(
+            LinkCheckResult::Failed("Reference definition '[ref]' is never used.".to_string()),
+            issue_result(&unused, &config)
+        );
+        let undefined_label = LinkIssue::UndefinedLabel("install".to_string());
+        assert_eq!(
+            LinkCheckResult::Failed("Label 'install' is not defined in the document.".to_string()),
+            issue_result(&undefined_label, &config)
+        );
+        let missing_attachment = LinkIssue::MissingAttachment("plot.png".to_string());
+        assert_eq!(
+            LinkCheckResult::Failed("Attachment 'plot.png' is not part of the cell.".to_string()),
+            issue_result(&missing_attachment, &config)
+        );
+        let suppressed = LinkIssue::Suppressed("mlc-disable directive in line 1".to_string());
+        assert_eq!(
+            LinkCheckResult::Ignored(
+                "Ignore link because of mlc-disable directive in line 1.".to_string()
+            ),
+            issue_result(&suppressed, &config)
+        );
     }
 }
@@ -0,0 +1,236 @@
+//! Wiki links like `[[Page Name]]`, as used by GitHub wikis, Obsidian and Foam.
+//! Pages are files anywhere in the vault, which is the directory tree of the checked files.
+
+use crate::anchor_extractors::slug::SlugDialect;
+use crate::file_traversal::markup_type;
+use crate::link_extractors::link_extractor::MarkupLink;
+use crate::markup::MarkupType;
+use crate::Config;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// The files wiki links can point to
+pub struct Vault {
+    /// Files by their lower case name. Markdown files are found without their extension too.
+    files: HashMap<String, Vec<PathBuf>>,
+}
+
+impl Vault {
+    /// Indexes all files below the root directory, or the checked directory if there is none.
+    /// Hidden directories like `.git` or `.obsidian` are skipped.
+    #[must_use]
+    pub fn new(config: &Config) -> Self {
+        let root = config
+            .optional
+            .root_dir
+            .as_ref()
+            .unwrap_or(&config.directory);
+        let root = if root.is_file() {
+            root.parent()
+                .filter(|p| !p.as_os_str().is_empty())
+                .unwrap_or_else(|| Path::new("."))
+        } else {
+            root
+        };
+        info!("Indexing the wiki vault at '{:?}' ...", root);
+
+        let mut files: HashMap<String, Vec<PathBuf>> = HashMap::new();
+        for entry in WalkDir::new(root)
+            .follow_links(false)
+            .into_iter()
+            .filter_entry(|e| e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.'))
+            .filter_map(Result::ok)
+            .filter(|e| !e.file_type().is_dir())
+        {
+            let Ok(path) = fs::canonicalize(entry.path()) else {
+                continue;
+            };
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            if markup_type(&name, &[MarkupType::Markdown]).is_some() {
+                if let Some((stem, _extension)) = name.rsplit_once('.') {
+                    files
+                        .entry(stem.to_string())
+                        .or_default()
+                        .push(path.clone());
+                }
+            }
+            files.entry(name).or_default().push(path);
+        }
+        Self { files }
+    }
+
+    /// The file and fragment a wiki link points to, or `None` if the link is no wiki link.
+    /// Pages which are not part of the vault resolve to a missing file next to the source document.
+    #[must_use]
+    pub fn resolve(
+        &self,
+        link: &MarkupLink,
+        slug_dialect: SlugDialect,
+    ) -> Option<(String, Option<String>)> {
+        let (page, fragment) = parse(&link.target)?;
+        let source = fs::canonicalize(&link.source).unwrap_or_else(|_| PathBuf::from(&link.source));
+        let target = if page.is_empty() {
+            source
+        } else {
+            self.find(page, &source)
+        };
+        Some((
+            target.to_string_lossy().to_string(),
+            fragment.map(|f| heading_or_block(f, slug_dialect)),
+        ))
+    }
+
+    /// The file of a page, preferring the directory of the source document and short paths
+    /// if several files have the same name
+    fn find(&self, page: &str, source: &Path) -> PathBuf {
+        let page = page.replace('\\', "/");
+        let page_lower = page.to_lowercase();
+        let (dirs, name) = page_lower.rsplit_once('/').unwrap_or(("", &page_lower));
+        let source_dir = source.parent().unwrap_or(source);
+        // GitHub wikis replace the spaces of page names with dashes
+        [name.to_string(), name.replace(' ', "-")]
+            .iter()
+            .filter_map(|name| self.files.get(name))
+            .flatten()
+            .filter(|path| in_dirs(path, dirs))
+            .min_by_key(|path| (path.parent() != Some(source_dir), path.components().count()))
+            .cloned()
+            .unwrap_or_else(|| source_dir.join(page))
+    }
+}
+
+/// Splits wiki link targets like `[[Page#Heading]]` or `![[image.png]]`
+/// into the page and the heading or block
+fn parse(target: &str) -> Option<(&str, Option<&str>)> {
+    let inner = target
+        .strip_prefix('!')
+        .unwrap_or(target)
+        .strip_prefix("[[")?
+        .strip_suffix("]]")?;
+    Some(match inner.split_once('#') {
+        Some((page, fragment)) => (page.trim(), Some(fragment)),
+        None => (inner.trim(), None),
+    })
+}
+
+/// The anchor of a heading, or a block reference like `^summary` as it is.
+/// Nested headings like `Chapter#Section` refer to the last one.
+fn heading_or_block(fragment: &str, slug_dialect: SlugDialect) -> String {
+    let last = fragment.rsplit('#').next().unwrap_or(fragment).trim();
+    if last.starts_with('^') {
+        last.to_string()
+    } else {
+        slug_dialect.slug(last)
+    }
+}
+
+/// Whether the parent directories of `path` end with the `/` separated `dirs`, ignoring case
+fn in_dirs(path: &Path, dirs: &str) -> bool {
+    let dirs: Vec<&str> = dirs
+        .split('/')
+        .filter(|d| !d.is_empty() && *d != ".")
+        .collect();
+    let parents: Vec<String> = path
+        .parent()
+        .map(|p| {
+            p.components()
+                .map(|c| c.as_os_str().to_string_lossy().to_lowercase())
+                .collect()
+        })
+        .unwrap_or_default();
+    dirs.len() <= parents.len()
+        && parents
+            .iter()
+            .rev()
+            .zip(dirs.iter().rev())
+            .all(|(parent, dir)| parent == dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntest::test_case;
+
+    fn vault_file(file: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("benches")
+            .join("benchmark")
+            .join("markdown")
+            .join("wiki")
+            .join(file)
+    }
+
+    fn resolve(target: &str) -> (String, Option<String>) {
+        let config = Config {
+            directory: vault_file(""),
+            ..Default::default()
+        };
+        let link = MarkupLink {
+            source: vault_file("Home.md").to_string_lossy().to_string(),
+            target: target.to_string(),
+            line: 1,
+            column: 1,
+            ..MarkupLink::default()
+        };
+        Vault::new(&config)
+            .resolve(&link, SlugDialect::GitHub)
+            .unwrap()
+    }
+
+    #[test_case("[[Page]]", "Page")]
+    #[test_case("![[image.png]]", "image.png")]
+    #[test_case("[[ Page Name ]]", "Page Name")]
+    fn parse_page(target: &str, page: &str) {
+        assert_eq!(Some((page, None)), parse(target));
+    }
+
+    #[test_case("[[ Page #Heading]]", "Page", "Heading")]
+    #[test_case("[[#^block]]", "", "^block")]
+    fn parse_page_and_fragment(target: &str, page: &str, fragment: &str) {
+        assert_eq!(Some((page, Some(fragment))), parse(target));
+    }
+
+    #[test_case("Page")]
+    #[test_case("[Page]")]
+    #[test_case("./page.md")]
+    fn no_wiki_link(target: &str) {
+        assert_eq!(None, parse(target));
+    }
+
+    #[test_case("Install Steps", "install-steps")]
+    #[test_case("Chapter#Section 1", "section-1")]
+    #[test_case("^summary", "^summary")]
+    #[test_case("Chapter#^summary", "^summary")]
+    fn fragment(fragment: &str, anchor: &str) {
+        assert_eq!(anchor, heading_or_block(fragment, SlugDialect::GitHub));
+    }
+
+    #[test_case("[[Getting Started]]", "Getting-Started.md")]
+    #[test_case("[[getting-started]]", "Getting-Started.md")]
+    #[test_case("[[setup guide]]", "notes/Setup Guide.md")]
+    #[test_case("[[Notes/Setup Guide]]", "notes/Setup Guide.md")]
+    #[test_case("[[Setup Guide.md]]", "notes/Setup Guide.md")]
+    #[test_case("![[diagram.png]]", "assets/diagram.png")]
+    #[test_case("[[#Overview]]", "Home.md")]
+    fn resolve_page(target: &str, file: &str) {
+        let expected = fs::canonicalize(vault_file(file)).unwrap();
+        assert_eq!(expected.to_string_lossy(), resolve(target).0);
+    }
+
+    #[test_case("[[Missing Page]]")]
+    #[test_case("[[assets/Setup Guide]]")]
+    #[test_case("[[diagram]]")]
+    fn unresolved_page(target: &str) {
+        assert!(!Path::new(&resolve(target).0).exists());
+    }
+
+    #[test]
+    fn resolve_fragment() {
+        assert_eq!(
+            Some("install-steps".to_string()),
+            resolve("[[Setup Guide#Install Steps]]").1
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use serde::Deserialize;
+use std::fmt;
 use std::str::FromStr;
 
 #[derive(Debug)]
@@ -7,10 +8,19 @@ pub struct MarkupFile {
     pub path: String,
 }
 
-#[derive(Debug, Clone, Copy, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
 pub enum MarkupType {
     Markdown,
+    Mdx,
     Html,
+    Rst,
+    AsciiDoc,
+    Org,
+    Latex,
+    Notebook,
+    Svg,
+    SourceCode,
+    Plain,
 }
 
 impl FromStr for MarkupType {
@@ -19,13 +29,37 @@ impl FromStr for MarkupType {
     fn from_str(s: &str) -> Result<Self, ()> {
         match s {
             "md" => Ok(Self::Markdown),
+            "mdx" => Ok(Self::Mdx),
             "html" => Ok(Self::Html),
+            "rst" => Ok(Self::Rst),
+            "adoc" => Ok(Self::AsciiDoc),
+            "org" => Ok(Self::Org),
+            "tex" => Ok(Self::Latex),
+            "ipynb" => Ok(Self::Notebook),
+            "svg" => Ok(Self::Svg),
+            "code" => Ok(Self::SourceCode),
+            "plain" => Ok(Self::Plain),
             _ => Err(()),
         }
     }
 }
 
 impl MarkupType {
+    /// All supported markup types
+    pub const ALL: [Self; 11] = [
+        Self::Markdown,
+        Self::Mdx,
+        Self::Html,
+        Self::Rst,
+        Self::AsciiDoc,
+        Self::Org,
+        Self::Latex,
+        Self::Notebook,
+        Self::Svg,
+        Self::SourceCode,
+        Self::Plain,
+    ];
+
     #[must_use]
     pub fn file_extensions(&self) -> Vec<String> {
         match self {
@@ -41,18 +75,65 @@ impl MarkupType {
                 "text".to_string(),
                 "rmd".to_string(),
             ],
+            Self::Mdx => vec!["mdx".to_string()],
             Self::Html => vec!["htm".to_string(), "html".to_string(), "xhtml".to_string()],
+            Self::Rst => vec!["rst".to_string(), "rest".to_string()],
+            Self::AsciiDoc => vec![
+                "adoc".to_string(),
+                "asciidoc".to_string(),
+                "asc".to_string(),
+            ],
+            Self::Org => vec!["org".to_string()],
+            Self::Latex => vec!["tex".to_string()],
+            Self::Notebook => vec!["ipynb".to_string()],
+            Self::Svg => vec!["svg".to_string()],
+            // Further extensions are added with the `comment-syntax` option
+            Self::SourceCode => vec!["rs".to_string()],
+            // Plain files are matched by the globs of the `plain-files` option
+            Self::Plain => vec![],
+        }
+    }
+}
+
+/// The flavor of markdown files, which decides the supported syntax extensions
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum MarkdownDialect {
+    /// GitHub flavored markdown with tables, footnotes, task lists, strikethrough and autolinks
+    #[default]
+    GitHub,
+    /// Plain `CommonMark` without extensions
+    CommonMark,
+}
+
+impl FromStr for MarkdownDialect {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "github" => Ok(Self::GitHub),
+            "commonmark" => Ok(Self::CommonMark),
+            _ => Err(()),
         }
     }
 }
 
+impl fmt::Display for MarkdownDialect {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::GitHub => "github",
+            Self::CommonMark => "commonmark",
+        };
+        write!(f, "{name}")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn all_lowercase_file_extensions() {
-        for mt in [MarkupType::Markdown, MarkupType::Html] {
+        for mt in MarkupType::ALL {
             for ext in mt.file_extensions() {
                 assert_eq!(ext, ext.to_lowercase());
             }
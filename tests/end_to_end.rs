@@ -2,9 +2,12 @@
 mod helper;
 
 use helper::benches_dir;
+use mlc::anchor_extractors::slug::SlugDialect;
+use mlc::link_extractors::link_extractor::LinkKind;
 use mlc::markup::MarkupType;
 use mlc::Config;
 use mlc::OptionalConfig;
+use regex::Regex;
 use std::convert::TryInto;
 
 #[tokio::test]
@@ -25,8 +28,34 @@ async fn end_to_end() {
                 "./benches/benchmark/markdown/ignore_me_dir"
                     .try_into()
                     .unwrap(),
+                "benches/benchmark/markdown/HashLinks.md"
+                    .try_into()
+                    .unwrap(),
+                "benches/benchmark/markdown/anchor_links.md"
+                    .try_into()
+                    .unwrap(),
+                "benches/benchmark/markdown/anchor_links_2.md"
+                    .try_into()
+                    .unwrap(),
+                "benches/benchmark/markdown/reference_link.md"
+                    .try_into()
+                    .unwrap(),
+                "benches/benchmark/markdown/ref_links.md"
+                    .try_into()
+                    .unwrap(),
+                "benches/benchmark/link_kinds".try_into().unwrap(),
             ]),
             root_dir: None,
+            http_fragments: None,
+            slug_dialect: None,
+            markdown_dialect: None,
+            front_matter_keys: None,
+            wiki_links: None,
+            notebook_outputs: None,
+            unused_references: None,
+            comment_syntax: None,
+            plain_files: None,
+            link_kinds: None,
         },
     };
     if let Err(e) = mlc::run(&config).await {
@@ -48,9 +77,350 @@ async fn end_to_end_different_root() {
             ignore_paths: None,
             throttle: None,
             root_dir: Some(test_files),
+            http_fragments: None,
+            slug_dialect: None,
+            markdown_dialect: None,
+            front_matter_keys: None,
+            wiki_links: None,
+            notebook_outputs: None,
+            unused_references: None,
+            comment_syntax: None,
+            plain_files: None,
+            link_kinds: None,
         },
     };
     if let Err(e) = mlc::run(&config).await {
         panic!("Test with custom root failed. {:?}", e);
     }
 }
+
+#[tokio::test]
+async fn end_to_end_broken_anchors() {
+    for file in [
+        "ignore_me_dir/broken_anchors.md",
+        "HashLinks.md",
+        "anchor_links.md",
+        "anchor_links_2.md",
+    ] {
+        let config = Config {
+            directory: benches_dir().join("benchmark").join("markdown").join(file),
+            optional: OptionalConfig {
+                markup_types: Some(vec![MarkupType::Markdown]),
+                offline: Some(true),
+                ..Default::default()
+            },
+        };
+        assert!(
+            mlc::run(&config).await.is_err(),
+            "{} has no broken anchors",
+            file
+        );
+    }
+}
+
+#[tokio::test]
+async fn end_to_end_undefined_references() {
+    for file in [
+        "ignore_me_dir/undefined_reference.md",
+        "reference_link.md",
+        "ref_links.md",
+    ] {
+        let config = Config {
+            directory: benches_dir().join("benchmark").join("markdown").join(file),
+            optional: OptionalConfig {
+                markup_types: Some(vec![MarkupType::Markdown]),
+                offline: Some(true),
+                ..Default::default()
+            },
+        };
+        assert!(
+            mlc::run(&config).await.is_err(),
+            "{} has no undefined references",
+            file
+        );
+    }
+}
+
+#[tokio::test]
+async fn end_to_end_same_document_anchors() {
+    let config = Config {
+        directory: benches_dir()
+            .join("benchmark")
+            .join("markdown")
+            .join("section_links.md"),
+        optional: OptionalConfig {
+            markup_types: Some(vec![MarkupType::Markdown]),
+            offline: Some(true),
+            ..Default::default()
+        },
+    };
+    if let Err(e) = mlc::run(&config).await {
+        panic!("Test with same document anchors failed. {:?}", e);
+    }
+}
+
+#[tokio::test]
+async fn end_to_end_gfm_extensions() {
+    let config = Config {
+        directory: benches_dir()
+            .join("benchmark")
+            .join("markdown")
+            .join("gfm_extensions.md"),
+        optional: OptionalConfig {
+            markup_types: Some(vec![MarkupType::Markdown]),
+            offline: Some(true),
+            ..Default::default()
+        },
+    };
+    if let Err(e) = mlc::run(&config).await {
+        panic!("Test with GitHub flavored markdown failed. {:?}", e);
+    }
+}
+
+#[tokio::test]
+async fn end_to_end_front_matter() {
+    let config = Config {
+        directory: benches_dir()
+            .join("benchmark")
+            .join("markdown")
+            .join("front_matter"),
+        optional: OptionalConfig {
+            markup_types: Some(vec![MarkupType::Markdown]),
+            offline: Some(true),
+            front_matter_keys: Some(vec![
+                "image".to_string(),
+                "redirect_from".to_string(),
+                "aliases".to_string(),
+            ]),
+            ..Default::default()
+        },
+    };
+    if let Err(e) = mlc::run(&config).await {
+        panic!("Test with front matter failed. {:?}", e);
+    }
+}
+
+#[tokio::test]
+async fn end_to_end_wiki_links() {
+    let config = Config {
+        directory: benches_dir()
+            .join("benchmark")
+            .join("markdown")
+            .join("wiki"),
+        optional: OptionalConfig {
+            markup_types: Some(vec![MarkupType::Markdown]),
+            offline: Some(true),
+            wiki_links: Some(true),
+            ..Default::default()
+        },
+    };
+    if let Err(e) = mlc::run(&config).await {
+        panic!("Test with wiki links failed. {:?}", e);
+    }
+}
+
+#[tokio::test]
+async fn end_to_end_mdx() {
+    let config = Config {
+        directory: benches_dir().join("benchmark").join("mdx"),
+        optional: OptionalConfig {
+            markup_types: Some(vec![MarkupType::Mdx, MarkupType::Markdown]),
+            offline: Some(true),
+            slug_dialect: Some(SlugDialect::Docusaurus),
+            ..Default::default()
+        },
+    };
+    if let Err(e) = mlc::run(&config).await {
+        panic!("Test with MDX files of a Docusaurus site failed. {:?}", e);
+    }
+}
+
+#[tokio::test]
+async fn end_to_end_rst() {
+    let config = Config {
+        directory: benches_dir().join("benchmark").join("rst"),
+        optional: OptionalConfig {
+            markup_types: Some(vec![MarkupType::Rst]),
+            offline: Some(true),
+            ..Default::default()
+        },
+    };
+    if let Err(e) = mlc::run(&config).await {
+        panic!("Test with reStructuredText files failed. {:?}", e);
+    }
+}
+
+#[tokio::test]
+async fn end_to_end_asciidoc() {
+    let config = Config {
+        directory: benches_dir().join("benchmark").join("asciidoc"),
+        optional: OptionalConfig {
+            markup_types: Some(vec![MarkupType::AsciiDoc]),
+            offline: Some(true),
+            ..Default::default()
+        },
+    };
+    if let Err(e) = mlc::run(&config).await {
+        panic!("Test with AsciiDoc files failed. {:?}", e);
+    }
+}
+
+#[tokio::test]
+async fn end_to_end_org() {
+    let config = Config {
+        directory: benches_dir().join("benchmark").join("org"),
+        optional: OptionalConfig {
+            markup_types: Some(vec![MarkupType::Org]),
+            offline: Some(true),
+            ..Default::default()
+        },
+    };
+    if let Err(e) = mlc::run(&config).await {
+        panic!("Test with Org files failed. {:?}", e);
+    }
+}
+
+#[tokio::test]
+async fn end_to_end_latex() {
+    let config = Config {
+        directory: benches_dir().join("benchmark").join("latex"),
+        optional: OptionalConfig {
+            markup_types: Some(vec![MarkupType::Latex]),
+            offline: Some(true),
+            ..Default::default()
+        },
+    };
+    if let Err(e) = mlc::run(&config).await {
+        panic!("Test with LaTeX files failed. {:?}", e);
+    }
+}
+
+#[tokio::test]
+async fn end_to_end_notebook() {
+    let config = Config {
+        directory: benches_dir().join("benchmark").join("notebook"),
+        optional: OptionalConfig {
+            markup_types: Some(vec![MarkupType::Notebook]),
+            offline: Some(true),
+            notebook_outputs: Some(true),
+            ..Default::default()
+        },
+    };
+    if let Err(e) = mlc::run(&config).await {
+        panic!("Test with Jupyter notebooks failed. {:?}", e);
+    }
+}
+
+#[tokio::test]
+async fn end_to_end_source_code() {
+    let config = Config {
+        directory: benches_dir().join("benchmark").join("source_code"),
+        optional: OptionalConfig {
+            markup_types: Some(vec![MarkupType::SourceCode]),
+            offline: Some(true),
+            comment_syntax: Some([("py".to_string(), vec!["#".to_string()])].into()),
+            ..Default::default()
+        },
+    };
+    if let Err(e) = mlc::run(&config).await {
+        panic!("Test with source code comments failed. {:?}", e);
+    }
+}
+
+#[tokio::test]
+async fn end_to_end_directives() {
+    let config = Config {
+        directory: benches_dir().join("benchmark").join("directives"),
+        optional: OptionalConfig {
+            markup_types: Some(vec![
+                MarkupType::Markdown,
+                MarkupType::Html,
+                MarkupType::Rst,
+                MarkupType::AsciiDoc,
+            ]),
+            ..Default::default()
+        },
+    };
+    if let Err(e) = mlc::run(&config).await {
+        panic!("Test with suppressed links failed. {:?}", e);
+    }
+}
+
+#[tokio::test]
+async fn end_to_end_svg() {
+    let config = Config {
+        directory: benches_dir().join("benchmark").join("svg"),
+        optional: OptionalConfig {
+            markup_types: Some(vec![MarkupType::Svg, MarkupType::Markdown]),
+            offline: Some(true),
+            ..Default::default()
+        },
+    };
+    if let Err(e) = mlc::run(&config).await {
+        panic!("Test with svg files failed. {:?}", e);
+    }
+}
+
+#[tokio::test]
+async fn end_to_end_link_kinds() {
+    let config = Config {
+        directory: benches_dir().join("benchmark").join("link_kinds"),
+        optional: OptionalConfig {
+            markup_types: Some(vec![MarkupType::Markdown]),
+            offline: Some(true),
+            link_kinds: Some(vec![LinkKind::Image]),
+            ..Default::default()
+        },
+    };
+    if let Err(e) = mlc::run(&config).await {
+        panic!("Test with link kinds failed. {:?}", e);
+    }
+}
+
+#[tokio::test]
+async fn end_to_end_plain() {
+    let config = Config {
+        directory: benches_dir().join("benchmark").join("plain"),
+        optional: OptionalConfig {
+            markup_types: Some(vec![MarkupType::Plain]),
+            offline: Some(true),
+            plain_files: Some(
+                [
+                    ("*.txt".to_string(), vec![]),
+                    ("Dockerfile".to_string(), vec![]),
+                    (
+                        "ci/*.yml".to_string(),
+                        vec![
+                            Regex::new(r"script: (\S+)").unwrap(),
+                            Regex::new(r#""(https?://[^"]+)""#).unwrap(),
+                        ],
+                    ),
+                    (
+                        "*.proto".to_string(),
+                        vec![Regex::new(r#"import "(?P<link>[^"]+)";"#).unwrap()],
+                    ),
+                ]
+                .into(),
+            ),
+            ..Default::default()
+        },
+    };
+    if let Err(e) = mlc::run(&config).await {
+        panic!("Test with plain files failed. {:?}", e);
+    }
+}
+
+#[tokio::test]
+async fn end_to_end_html_base() {
+    let config = Config {
+        directory: benches_dir().join("benchmark").join("html").join("base"),
+        optional: OptionalConfig {
+            markup_types: Some(vec![MarkupType::Html]),
+            offline: Some(true),
+            ..Default::default()
+        },
+    };
+    if let Err(e) = mlc::run(&config).await {
+        panic!("Test with html base elements failed. {:?}", e);
+    }
+}